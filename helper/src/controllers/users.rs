@@ -2,6 +2,7 @@ use axum::{extract::State, response::IntoResponse, Json};
 use open_wechat::{
     client::Client,
     credential::{CheckSessionKey, GenericAccessToken, GetAccessToken, ResetSessionKey},
+    id::{LoginCode, OpenId, SessionKey},
 };
 use serde::Deserialize;
 use serde_json::json;
@@ -9,10 +10,9 @@ use tracing::{event, Level};
 
 use crate::{controllers::JsonDecoder, Result};
 
-#[derive(Deserialize, Default)]
-#[serde(default)]
+#[derive(Deserialize)]
 pub(crate) struct Logger {
-    code: String,
+    code: LoginCode,
 }
 
 pub(crate) async fn login(
@@ -26,9 +26,9 @@ pub(crate) async fn login(
     })))
 }
 
-#[derive(Deserialize, Default)]
+#[derive(Deserialize)]
 pub(crate) struct EncryptedPayload {
-    code: String,
+    code: LoginCode,
     encrypted_data: String,
     iv: String,
 }
@@ -69,11 +69,10 @@ pub(crate) async fn get_access_token(
 //     })))
 // }
 
-#[derive(Debug, Deserialize, Default)]
-#[serde(default)]
+#[derive(Debug, Deserialize)]
 pub(crate) struct SessionKeyChecker {
-    session_key: String,
-    open_id: String,
+    session_key: SessionKey,
+    open_id: OpenId,
 }
 
 pub(crate) async fn check_session_key(
@@ -93,11 +92,10 @@ pub(crate) async fn check_session_key(
     })))
 }
 
-#[derive(Debug, Deserialize, Default)]
-#[serde(default)]
+#[derive(Debug, Deserialize)]
 pub(crate) struct SessionKeyResetter {
-    session_key: String,
-    open_id: String,
+    session_key: SessionKey,
+    open_id: OpenId,
 }
 
 pub(crate) async fn reset_session_key(
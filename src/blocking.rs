@@ -0,0 +1,456 @@
+//! 基于 `reqwest::blocking` 的同步客户端，用于批处理任务和命令行工具等不使用 tokio 运行时的场景
+//!
+//! 注意：不能在 tokio 运行时中调用，异步场景请使用 [`crate::client::Client`]
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::{event, instrument, Level};
+
+use crate::{
+    credential::{
+        AccessToken, AccessTokenBuilder, Credential, CredentialBuilder, SessionKeySignature,
+        StableAccessToken,
+    },
+    error::Error::InternalServer,
+    id::{LoginCode, OpenId, SessionKey},
+    response::Response,
+    secret::Secret,
+    user::{Contact, ContactBuilder, ContactRequest},
+    Result,
+};
+
+/// 存储微信小程序的 appid 和 secret，同步发送请求
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: Arc<ClientInner>,
+}
+
+impl Client {
+    /// ```ignore
+    /// use open_wechat::blocking::Client;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let app_id = "your app id";
+    ///     let secret = "your app secret";
+    ///
+    ///     let client = Client::new(app_id, secret);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new(app_id: &str, secret: &str) -> Self {
+        let client = reqwest::blocking::Client::new();
+
+        Self {
+            inner: Arc::new(ClientInner {
+                app_id: app_id.into(),
+                secret: Secret::new(secret),
+                client,
+            }),
+        }
+    }
+
+    pub fn app_id(&self) -> &str {
+        &self.inner.app_id
+    }
+
+    fn secret(&self) -> &str {
+        self.inner.secret.expose()
+    }
+
+    /// 携带 access_token 发送 POST 请求，请求体为 JSON
+    fn post<B, T>(&self, url: &str, access_token: &Secret, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let response = self
+            .inner
+            .client
+            .post(url)
+            .query(&[("access_token", access_token.expose())])
+            .json(body)
+            .send()?;
+
+        Self::extract(response)
+    }
+
+    /// 携带 access_token 发送 GET 请求
+    fn get<Q, T>(&self, url: &str, access_token: &Secret, query: &Q) -> Result<T>
+    where
+        Q: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let response = self
+            .inner
+            .client
+            .get(url)
+            .query(&[("access_token", access_token.expose())])
+            .query(query)
+            .send()?;
+
+        Self::extract(response)
+    }
+
+    fn extract<T>(response: reqwest::blocking::Response) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        event!(Level::DEBUG, "response status: {}", response.status());
+
+        if response.status().is_success() {
+            let response = response.json::<Response<T>>()?;
+
+            response.extract()
+        } else {
+            Err(InternalServer(response.text()?))
+        }
+    }
+
+    const AUTHENTICATION: &'static str = "https://api.weixin.qq.com/sns/jscode2session";
+
+    /// 登录凭证校验
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/code2Session.html
+    /// ```ignore
+    /// use open_wechat::{blocking::Client, id::LoginCode};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your app id", "your app secret");
+    ///
+    ///     let code: LoginCode = "your login code".parse()?;
+    ///
+    ///     let credential = client.login(&code)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[instrument(skip(self, code))]
+    pub fn login(&self, code: &LoginCode) -> Result<Credential> {
+        let mut map: HashMap<&str, &str> = HashMap::new();
+
+        map.insert("appid", &self.inner.app_id);
+        map.insert("secret", self.secret());
+        map.insert("js_code", code.as_str());
+        map.insert("grant_type", "authorization_code");
+
+        let response = self
+            .inner
+            .client
+            .get(Self::AUTHENTICATION)
+            .query(&map)
+            .send()?;
+
+        let credential = Self::extract::<CredentialBuilder>(response)?.build();
+
+        event!(Level::DEBUG, "credential: {:#?}", credential);
+
+        Ok(credential)
+    }
+
+    const ACCESS_TOKEN: &'static str = "https://api.weixin.qq.com/cgi-bin/token";
+
+    /// 获取小程序全局唯一后台接口调用凭据（access_token）
+    /// https://developers.weixin.qq.com/miniprogram/dev/api-backend/open-api/access-token/auth.getAccessToken.html
+    #[instrument(skip(self))]
+    fn get_access_token(&self) -> Result<AccessTokenBuilder> {
+        let mut map: HashMap<&str, &str> = HashMap::new();
+
+        map.insert("grant_type", "client_credential");
+        map.insert("appid", &self.inner.app_id);
+        map.insert("secret", self.secret());
+
+        let response = self
+            .inner
+            .client
+            .get(Self::ACCESS_TOKEN)
+            .query(&map)
+            .send()?;
+
+        let builder = Self::extract::<AccessTokenBuilder>(response)?;
+
+        event!(Level::DEBUG, "access token builder: {:#?}", builder);
+
+        Ok(builder)
+    }
+
+    const STABLE_ACCESS_TOKEN: &str = "https://api.weixin.qq.com/cgi-bin/stable_token";
+
+    /// 获取小程序全局唯一后台接口调用凭据（access_token）
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/mp-access-token/getStableAccessToken.html
+    #[instrument(skip(self, force_refresh))]
+    fn get_stable_access_token(&self, force_refresh: Option<bool>) -> Result<AccessTokenBuilder> {
+        let mut map: HashMap<&str, &str> = HashMap::new();
+
+        map.insert("grant_type", "client_credential");
+        map.insert("appid", &self.inner.app_id);
+        map.insert("secret", self.secret());
+
+        if let Some(force_refresh) = force_refresh {
+            event!(Level::DEBUG, "force_refresh: {}", force_refresh);
+
+            map.insert(
+                "force_refresh",
+                if force_refresh { "true" } else { "false" },
+            );
+        }
+
+        let response = self
+            .inner
+            .client
+            .post(Self::STABLE_ACCESS_TOKEN)
+            .json(&map)
+            .send()?;
+
+        let builder = Self::extract::<AccessTokenBuilder>(response)?;
+
+        event!(Level::DEBUG, "stable access token builder: {:#?}", builder);
+
+        Ok(builder)
+    }
+}
+
+#[derive(Debug)]
+struct ClientInner {
+    app_id: String,
+    secret: Secret,
+    client: reqwest::blocking::Client,
+}
+
+/// 同步的接口调用凭据，过期时由持有锁的调用者刷新，其余调用者阻塞等待
+/// ```ignore
+/// use open_wechat::blocking::{Client, GenericAccessToken, GetAccessToken};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::new("your app id", "your app secret");
+///
+///     let access_token = GenericAccessToken::new(client)?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenericAccessToken<T = AccessToken> {
+    inner: Arc<Mutex<T>>,
+    client: Client,
+}
+
+impl<T> GenericAccessToken<T> {
+    // 刷新失败不会破坏缓存的凭据，锁中毒时继续使用
+    fn lock(&self) -> MutexGuard<'_, T> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+pub trait GetAccessToken {
+    fn new(client: Client) -> Result<Self>
+    where
+        Self: Sized;
+
+    fn access_token(&self) -> Result<Secret>;
+}
+
+impl GetAccessToken for GenericAccessToken<AccessToken> {
+    fn new(client: Client) -> Result<Self> {
+        let builder = client.get_access_token()?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(AccessToken {
+                access_token: builder.access_token,
+                expired_at: builder.expired_at,
+            })),
+            client,
+        })
+    }
+
+    fn access_token(&self) -> Result<Secret> {
+        let mut guard = self.lock();
+
+        if guard.expired_at <= Utc::now() {
+            event!(Level::DEBUG, "expired at: {}", guard.expired_at);
+
+            let builder = self.client.get_access_token()?;
+
+            guard.access_token = builder.access_token;
+            guard.expired_at = builder.expired_at;
+
+            event!(Level::DEBUG, "fresh access token: {:#?}", guard);
+        }
+
+        Ok(guard.access_token.clone())
+    }
+}
+
+pub trait GetStableAccessToken {
+    fn new(client: Client, force_refresh: impl Into<Option<bool>>) -> Result<Self>
+    where
+        Self: Sized;
+
+    fn access_token(&self) -> Result<Secret>;
+
+    fn set_force_refresh(&self, force_refresh: bool);
+}
+
+impl GetStableAccessToken for GenericAccessToken<StableAccessToken> {
+    fn new(client: Client, force_refresh: impl Into<Option<bool>>) -> Result<Self> {
+        let force_refresh = force_refresh.into();
+
+        let builder = client.get_stable_access_token(force_refresh)?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(StableAccessToken {
+                access_token: builder.access_token,
+                expired_at: builder.expired_at,
+                force_refresh,
+            })),
+            client,
+        })
+    }
+
+    fn access_token(&self) -> Result<Secret> {
+        let mut guard = self.lock();
+
+        if guard.expired_at <= Utc::now() {
+            event!(Level::DEBUG, "expired at: {}", guard.expired_at);
+
+            let builder = self.client.get_stable_access_token(guard.force_refresh)?;
+
+            guard.access_token = builder.access_token;
+            guard.expired_at = builder.expired_at;
+
+            event!(Level::DEBUG, "fresh stable access token: {:#?}", guard);
+        }
+
+        Ok(guard.access_token.clone())
+    }
+
+    fn set_force_refresh(&self, force_refresh: bool) {
+        self.lock().force_refresh = Some(force_refresh);
+    }
+}
+
+/// 同步版本的 [`crate::credential::TokenProvider`]，本模块中需要 access_token 的接口都基于此 trait 实现
+pub trait TokenProvider: Send + Sync {
+    /// 发送请求使用的 [`Client`]
+    fn client(&self) -> &Client;
+
+    /// 有效的接口调用凭据
+    fn token(&self) -> Result<Secret>;
+}
+
+impl TokenProvider for GenericAccessToken<AccessToken> {
+    fn client(&self) -> &Client {
+        &self.client
+    }
+
+    fn token(&self) -> Result<Secret> {
+        GetAccessToken::access_token(self)
+    }
+}
+
+impl TokenProvider for GenericAccessToken<StableAccessToken> {
+    fn client(&self) -> &Client {
+        &self.client
+    }
+
+    fn token(&self) -> Result<Secret> {
+        GetStableAccessToken::access_token(self)
+    }
+}
+
+pub trait CheckSessionKey {
+    const CHECK_SESSION_KEY: &'static str = "https://api.weixin.qq.com/wxa/checksession";
+
+    /// 检查登录态是否过期
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/checkSessionKey.html
+    fn check_session_key(&self, session_key: &SessionKey, open_id: &OpenId) -> Result<()>;
+}
+
+impl<T> CheckSessionKey for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, session_key, open_id))]
+    fn check_session_key(&self, session_key: &SessionKey, open_id: &OpenId) -> Result<()> {
+        let query = SessionKeySignature::new(session_key, open_id)?;
+
+        self.client()
+            .get(Self::CHECK_SESSION_KEY, &self.token()?, &query)
+    }
+}
+
+pub trait ResetSessionKey {
+    const RESET_SESSION_KEY: &'static str = "https://api.weixin.qq.com/wxa/resetusersessionkey";
+
+    /// 重置用户的 session_key
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/ResetUserSessionKey.html
+    fn reset_session_key(&self, session_key: &SessionKey, open_id: &OpenId) -> Result<Credential>;
+}
+
+impl<T> ResetSessionKey for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, session_key, open_id))]
+    fn reset_session_key(&self, session_key: &SessionKey, open_id: &OpenId) -> Result<Credential> {
+        let query = SessionKeySignature::new(session_key, open_id)?;
+
+        let builder = self.client().get::<_, CredentialBuilder>(
+            Self::RESET_SESSION_KEY,
+            &self.token()?,
+            &query,
+        )?;
+
+        let credential = builder.build();
+
+        event!(Level::DEBUG, "credential: {:#?}", credential);
+
+        Ok(credential)
+    }
+}
+
+pub trait GetContact {
+    const PHONE: &str = "https://api.weixin.qq.com/wxa/business/getuserphonenumber";
+
+    /// 获取手机号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/phone-number/getPhoneNumber.html
+    /// ```ignore
+    /// use open_wechat::blocking::{Client, GenericAccessToken, GetAccessToken, GetContact};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your app id", "your app secret");
+    ///
+    ///     let access_token = GenericAccessToken::new(client)?;
+    ///
+    ///     let contact = access_token.get_contact("your code", None)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn get_contact(&self, code: &str, open_id: Option<&OpenId>) -> Result<Contact>;
+}
+
+impl<T> GetContact for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, code))]
+    fn get_contact(&self, code: &str, open_id: Option<&OpenId>) -> Result<Contact> {
+        let request = ContactRequest {
+            code,
+            open_id: open_id.map(OpenId::as_str),
+        };
+
+        let builder =
+            self.client()
+                .post::<_, ContactBuilder>(Self::PHONE, &self.token()?, &request)?;
+
+        event!(Level::DEBUG, "contact builder: {:#?}", builder);
+
+        Ok(builder.build())
+    }
+}
@@ -1,14 +1,23 @@
 use std::{collections::HashMap, sync::Arc};
 
+use http::header::CONTENT_TYPE;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::from_slice;
 use tracing::{event, instrument, Level};
 
 use crate::{
     credential::{AccessTokenBuilder, Credential, CredentialBuilder},
     error::Error::InternalServer,
+    id::LoginCode,
     response::Response,
+    secret::Secret,
+    transport::{HttpRequest, HttpResponse, HttpTransport, Multipart},
     Result,
 };
 
+#[cfg(feature = "reqwest")]
+use crate::transport::ReqwestTransport;
+
 /// 存储微信小程序的 appid 和 secret
 #[derive(Debug, Clone)]
 pub struct Client {
@@ -29,20 +38,178 @@ impl Client {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg(feature = "reqwest")]
     pub fn new(app_id: &str, secret: &str) -> Self {
-        let client = reqwest::Client::new();
+        Self::with_transport(app_id, secret, ReqwestTransport::default())
+    }
 
+    /// 使用自定义的传输层发送请求，如自有的 HTTP 客户端或测试中不发送请求的传输层
+    /// ```ignore
+    /// use open_wechat::{client::Client, transport::ReqwestTransport};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let request = reqwest::Client::builder()
+    ///         .timeout(Duration::from_secs(10))
+    ///         .build()?;
+    ///
+    ///     let client = Client::with_transport(
+    ///         "your app id",
+    ///         "your app secret",
+    ///         ReqwestTransport::new(request),
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_transport(
+        app_id: &str,
+        secret: &str,
+        transport: impl HttpTransport + 'static,
+    ) -> Self {
         Self {
             inner: Arc::new(ClientInner {
                 app_id: app_id.into(),
-                secret: secret.into(),
-                client,
+                secret: Secret::new(secret),
+                transport: Arc::new(transport),
             }),
         }
     }
 
-    pub(crate) fn request(&self) -> &reqwest::Client {
-        &self.inner.client
+    pub fn app_id(&self) -> &str {
+        &self.inner.app_id
+    }
+
+    pub(crate) fn secret(&self) -> &str {
+        self.inner.secret.expose()
+    }
+
+    /// 通过传输层发送请求
+    pub(crate) async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let response = self.inner.transport.send(request).await?;
+
+        event!(Level::DEBUG, "response status: {}", response.status);
+
+        Ok(response)
+    }
+
+    /// 携带 access_token 发送 POST 请求，请求体为 JSON
+    pub(crate) async fn post<B, T>(&self, url: &str, access_token: &Secret, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.post_with_query(url, access_token, &(), body).await
+    }
+
+    /// 携带 access_token 和其他查询参数发送 POST 请求，请求体为 JSON
+    pub(crate) async fn post_with_query<Q, B, T>(
+        &self,
+        url: &str,
+        access_token: &Secret,
+        query: &Q,
+        body: &B,
+    ) -> Result<T>
+    where
+        Q: Serialize + ?Sized,
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let request = HttpRequest::post(url)?
+            .query(&[("access_token", access_token.expose())])?
+            .query(query)?
+            .json(body)?;
+
+        self.send(request).await?.extract()
+    }
+
+    /// 携带 access_token 和其他查询参数发送 POST 请求，不发送请求体
+    pub(crate) async fn post_query<Q, T>(
+        &self,
+        url: &str,
+        access_token: &Secret,
+        query: &Q,
+    ) -> Result<T>
+    where
+        Q: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let request = HttpRequest::post(url)?
+            .query(&[("access_token", access_token.expose())])?
+            .query(query)?;
+
+        self.send(request).await?.extract()
+    }
+
+    /// 携带 access_token 和其他查询参数发送 POST 请求，请求体为 multipart/form-data
+    pub(crate) async fn post_multipart<Q, T>(
+        &self,
+        url: &str,
+        access_token: &Secret,
+        query: &Q,
+        form: Multipart,
+    ) -> Result<T>
+    where
+        Q: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let request = HttpRequest::post(url)?
+            .query(&[("access_token", access_token.expose())])?
+            .query(query)?
+            .multipart(form)?;
+
+        self.send(request).await?.extract()
+    }
+
+    /// 携带 access_token 发送 GET 请求
+    pub(crate) async fn get<Q, T>(&self, url: &str, access_token: &Secret, query: &Q) -> Result<T>
+    where
+        Q: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let request = HttpRequest::get(url)?
+            .query(&[("access_token", access_token.expose())])?
+            .query(query)?;
+
+        self.send(request).await?.extract()
+    }
+
+    /// 携带 access_token 发送 GET 请求并下载文件，微信返回 JSON 时视为错误
+    pub(crate) async fn download<Q>(
+        &self,
+        url: &str,
+        access_token: &Secret,
+        query: &Q,
+    ) -> Result<Vec<u8>>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let request = HttpRequest::get(url)?
+            .query(&[("access_token", access_token.expose())])?
+            .query(query)?;
+
+        let response = self.send(request).await?;
+
+        if !response.status.is_success() {
+            return Err(InternalServer(response.text()));
+        }
+
+        let json = response
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| {
+                value.starts_with("application/json") || value.starts_with("text/plain")
+            });
+
+        if json {
+            from_slice::<Response<()>>(&response.body)?.extract()?;
+
+            return Err(InternalServer(response.text()));
+        }
+
+        Ok(response.body)
     }
 
     const AUTHENTICATION: &'static str = "https://api.weixin.qq.com/sns/jscode2session";
@@ -51,13 +218,12 @@ impl Client {
     /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/code2Session.html
     /// ```rust
     /// use axum::{extract::State, response::IntoResponse, Json};
-    /// use open_wechat::{client::Client, Result};
+    /// use open_wechat::{client::Client, id::LoginCode, Result};
     /// use serde::Deserialize;
     ///
-    /// #[derive(Deserialize, Default)]
-    /// #[serde(default)]
+    /// #[derive(Deserialize)]
     /// pub(crate) struct Logger {
-    ///     code: String,
+    ///     code: LoginCode,
     /// }
     ///
     /// pub(crate) async fn login(
@@ -70,37 +236,25 @@ impl Client {
     /// }
     /// ```
     #[instrument(skip(self, code))]
-    pub async fn login(&self, code: &str) -> Result<Credential> {
-        event!(Level::DEBUG, "code: {}", code);
-
+    pub async fn login(&self, code: &LoginCode) -> Result<Credential> {
         let mut map: HashMap<&str, &str> = HashMap::new();
 
         map.insert("appid", &self.inner.app_id);
-        map.insert("secret", &self.inner.secret);
-        map.insert("js_code", code);
+        map.insert("secret", self.secret());
+        map.insert("js_code", code.as_str());
         map.insert("grant_type", "authorization_code");
 
-        let response = self
-            .inner
-            .client
-            .get(Self::AUTHENTICATION)
-            .query(&map)
-            .send()
-            .await?;
-
-        event!(Level::DEBUG, "authentication response: {:#?}", response);
+        let request = HttpRequest::get(Self::AUTHENTICATION)?.query(&map)?;
 
-        if response.status().is_success() {
-            let response = response.json::<Response<CredentialBuilder>>().await?;
+        let credential = self
+            .send(request)
+            .await?
+            .extract::<CredentialBuilder>()?
+            .build();
 
-            let credential = response.extract()?.build();
+        event!(Level::DEBUG, "credential: {:#?}", credential);
 
-            event!(Level::DEBUG, "credential: {:#?}", credential);
-
-            Ok(credential)
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+        Ok(credential)
     }
 
     const ACCESS_TOKEN: &'static str = "https://api.weixin.qq.com/cgi-bin/token";
@@ -113,29 +267,15 @@ impl Client {
 
         map.insert("grant_type", "client_credential");
         map.insert("appid", &self.inner.app_id);
-        map.insert("secret", &self.inner.secret);
+        map.insert("secret", self.secret());
 
-        let response = self
-            .inner
-            .client
-            .get(Self::ACCESS_TOKEN)
-            .query(&map)
-            .send()
-            .await?;
+        let request = HttpRequest::get(Self::ACCESS_TOKEN)?.query(&map)?;
 
-        event!(Level::DEBUG, "response: {:#?}", response);
+        let builder = self.send(request).await?.extract::<AccessTokenBuilder>()?;
 
-        if response.status().is_success() {
-            let res = response.json::<Response<AccessTokenBuilder>>().await?;
+        event!(Level::DEBUG, "access token builder: {:#?}", builder);
 
-            let builder = res.extract()?;
-
-            event!(Level::DEBUG, "access token builder: {:#?}", builder);
-
-            Ok(builder)
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+        Ok(builder)
     }
 
     const STABLE_ACCESS_TOKEN: &str = "https://api.weixin.qq.com/cgi-bin/stable_token";
@@ -147,45 +287,34 @@ impl Client {
         &self,
         force_refresh: impl Into<Option<bool>>,
     ) -> Result<AccessTokenBuilder> {
-        let mut map: HashMap<&str, String> = HashMap::new();
+        let mut map: HashMap<&str, &str> = HashMap::new();
 
-        map.insert("grant_type", "client_credential".into());
-        map.insert("appid", self.inner.app_id.clone());
-        map.insert("secret", self.inner.secret.clone());
+        map.insert("grant_type", "client_credential");
+        map.insert("appid", &self.inner.app_id);
+        map.insert("secret", self.secret());
 
         if let Some(force_refresh) = force_refresh.into() {
             event!(Level::DEBUG, "force_refresh: {}", force_refresh);
 
-            map.insert("force_refresh", force_refresh.to_string());
+            map.insert(
+                "force_refresh",
+                if force_refresh { "true" } else { "false" },
+            );
         }
 
-        let response = self
-            .inner
-            .client
-            .post(Self::STABLE_ACCESS_TOKEN)
-            .json(&map)
-            .send()
-            .await?;
+        let request = HttpRequest::post(Self::STABLE_ACCESS_TOKEN)?.json(&map)?;
 
-        event!(Level::DEBUG, "response: {:#?}", response);
+        let builder = self.send(request).await?.extract::<AccessTokenBuilder>()?;
 
-        if response.status().is_success() {
-            let response = response.json::<Response<AccessTokenBuilder>>().await?;
+        event!(Level::DEBUG, "stable access token builder: {:#?}", builder);
 
-            let builder = response.extract()?;
-
-            event!(Level::DEBUG, "stable access token builder: {:#?}", builder);
-
-            Ok(builder)
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+        Ok(builder)
     }
 }
 
 #[derive(Debug)]
 struct ClientInner {
     app_id: String,
-    secret: String,
-    client: reqwest::Client,
+    secret: Secret,
+    transport: Arc<dyn HttpTransport>,
 }
@@ -0,0 +1,445 @@
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::from_str;
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, Result};
+
+#[derive(Serialize)]
+struct FunctionQuery<'a> {
+    env: &'a str,
+    name: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionBuilder {
+    resp_data: String,
+}
+
+#[derive(Serialize)]
+struct DatabaseRequest<'a> {
+    env: &'a str,
+    query: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseAddBuilder {
+    #[serde(default)]
+    id_list: Vec<String>,
+}
+
+/// 查询结果，记录为 JSON 字符串
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatabaseQuery {
+    pager: Pager,
+    #[serde(default)]
+    data: Vec<String>,
+}
+
+impl DatabaseQuery {
+    pub fn pager(&self) -> &Pager {
+        &self.pager
+    }
+
+    /// 原始的 JSON 字符串记录
+    pub fn data(&self) -> &[String] {
+        &self.data
+    }
+
+    /// 将记录反序列化为指定类型
+    pub fn records<D>(&self) -> Result<Vec<D>>
+    where
+        D: DeserializeOwned,
+    {
+        self.data
+            .iter()
+            .map(|record| Ok(from_str(record)?))
+            .collect()
+    }
+}
+
+/// 分页信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Pager {
+    #[serde(rename(deserialize = "Offset"))]
+    offset: u64,
+    #[serde(rename(deserialize = "Limit"))]
+    limit: u64,
+    #[serde(rename(deserialize = "Total"))]
+    total: u64,
+}
+
+impl Pager {
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// 更新结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatabaseUpdate {
+    matched: u64,
+    modified: u64,
+    #[serde(default)]
+    id: String,
+}
+
+impl DatabaseUpdate {
+    /// 匹配的记录数
+    pub fn matched(&self) -> u64 {
+        self.matched
+    }
+
+    /// 修改的记录数
+    pub fn modified(&self) -> u64 {
+        self.modified
+    }
+
+    /// 新增记录的 id，仅 upsert 时返回
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseDeleteBuilder {
+    deleted: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseAggregateBuilder {
+    #[serde(default)]
+    data: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseCountBuilder {
+    count: u64,
+}
+
+#[derive(Serialize)]
+struct UploadFileRequest<'a> {
+    env: &'a str,
+    path: &'a str,
+}
+
+/// 文件上传链接，使用返回的信息以 multipart/form-data 方式将文件 POST 到 `url`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UploadFile {
+    url: String,
+    token: String,
+    authorization: String,
+    file_id: String,
+    cos_file_id: String,
+}
+
+impl UploadFile {
+    /// 上传 url
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 上传表单中的 `x-cos-security-token` 字段
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// 上传表单中的 `Signature` 字段
+    pub fn authorization(&self) -> &str {
+        &self.authorization
+    }
+
+    /// 文件 ID
+    pub fn file_id(&self) -> &str {
+        &self.file_id
+    }
+
+    /// 上传表单中的 `x-cos-meta-fileid` 字段
+    pub fn cos_file_id(&self) -> &str {
+        &self.cos_file_id
+    }
+}
+
+/// 下载文件的参数
+#[derive(Debug, Serialize, Clone)]
+pub struct DownloadFile {
+    /// 文件 ID
+    #[serde(rename = "fileid")]
+    pub file_id: String,
+    /// 下载链接有效期，单位为秒
+    pub max_age: u64,
+}
+
+#[derive(Serialize)]
+struct DownloadFileRequest<'a> {
+    env: &'a str,
+    file_list: &'a [DownloadFile],
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadFileBuilder {
+    #[serde(default)]
+    file_list: Vec<DownloadLink>,
+}
+
+/// 文件下载链接
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadLink {
+    #[serde(rename(deserialize = "fileid"))]
+    file_id: String,
+    #[serde(default)]
+    download_url: String,
+    #[serde(default)]
+    status: i64,
+    #[serde(default)]
+    errmsg: String,
+}
+
+impl DownloadLink {
+    pub fn file_id(&self) -> &str {
+        &self.file_id
+    }
+
+    pub fn download_url(&self) -> &str {
+        &self.download_url
+    }
+
+    /// 状态码，0 为成功
+    pub fn status(&self) -> i64 {
+        self.status
+    }
+
+    pub fn errmsg(&self) -> &str {
+        &self.errmsg
+    }
+}
+
+/// 云开发 HTTP API，`env` 为云开发环境 ID，数据库操作的 `query` 为数据库操作语句，
+/// 如 `db.collection("geo").where({done: false}).get()`
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{cloud::ManageCloud, credential::GenericAccessToken, Result};
+/// use serde_json::{json, Value};
+///
+/// pub(crate) async fn todos(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let query = access_token
+///         .database_query("test2-4a89da", r#"db.collection("todos").limit(10).get()"#)
+///         .await?;
+///
+///     let todos = query.records::<Value>()?;
+///
+///     let result = access_token
+///         .invoke_cloud_function("test2-4a89da", "add", &json!({ "a": 1, "b": 2 }))
+///         .await?;
+///
+///     Ok(Json(json!({
+///         "total": query.pager().total(),
+///         "todos": todos,
+///         "result": result,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait ManageCloud {
+    const INVOKE_CLOUD_FUNCTION: &'static str = "https://api.weixin.qq.com/tcb/invokecloudfunction";
+    const DATABASE_ADD: &'static str = "https://api.weixin.qq.com/tcb/databaseadd";
+    const DATABASE_QUERY: &'static str = "https://api.weixin.qq.com/tcb/databasequery";
+    const DATABASE_UPDATE: &'static str = "https://api.weixin.qq.com/tcb/databaseupdate";
+    const DATABASE_DELETE: &'static str = "https://api.weixin.qq.com/tcb/databasedelete";
+    const DATABASE_AGGREGATE: &'static str = "https://api.weixin.qq.com/tcb/databaseaggregate";
+    const DATABASE_COUNT: &'static str = "https://api.weixin.qq.com/tcb/databasecount";
+    const UPLOAD_FILE: &'static str = "https://api.weixin.qq.com/tcb/uploadfile";
+    const BATCH_DOWNLOAD_FILE: &'static str = "https://api.weixin.qq.com/tcb/batchdownloadfile";
+
+    /// 触发云函数，`data` 作为云函数的 event 参数，返回云函数的返回值 JSON 字符串
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/functions/invokeCloudFunction.html
+    async fn invoke_cloud_function<B>(&self, env: &str, name: &str, data: &B) -> Result<String>
+    where
+        B: Serialize + Sync + ?Sized;
+
+    /// 数据库插入记录，返回插入成功的记录 id
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/database/databaseAdd.html
+    async fn database_add(&self, env: &str, query: &str) -> Result<Vec<String>>;
+
+    /// 数据库查询记录
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/database/databaseQuery.html
+    async fn database_query(&self, env: &str, query: &str) -> Result<DatabaseQuery>;
+
+    /// 数据库更新记录
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/database/databaseUpdate.html
+    async fn database_update(&self, env: &str, query: &str) -> Result<DatabaseUpdate>;
+
+    /// 数据库删除记录，返回删除的记录数
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/database/databaseDelete.html
+    async fn database_delete(&self, env: &str, query: &str) -> Result<u64>;
+
+    /// 数据库聚合，返回 JSON 字符串记录
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/database/databaseAggregate.html
+    async fn database_aggregate(&self, env: &str, query: &str) -> Result<Vec<String>>;
+
+    /// 统计集合记录数或统计查询语句对应的结果记录数
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/database/databaseCount.html
+    async fn database_count(&self, env: &str, query: &str) -> Result<u64>;
+
+    /// 获取文件上传链接，`path` 为上传路径
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/storage/uploadFile.html
+    async fn upload_file(&self, env: &str, path: &str) -> Result<UploadFile>;
+
+    /// 获取文件下载链接
+    /// https://developers.weixin.qq.com/miniprogram/dev/wxcloud/reference-http-api/storage/batchDownloadFile.html
+    async fn batch_download_file(
+        &self,
+        env: &str,
+        files: &[DownloadFile],
+    ) -> Result<Vec<DownloadLink>>;
+}
+
+#[async_trait]
+impl<T> ManageCloud for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, data))]
+    async fn invoke_cloud_function<B>(&self, env: &str, name: &str, data: &B) -> Result<String>
+    where
+        B: Serialize + Sync + ?Sized,
+    {
+        let builder = self
+            .client()
+            .post_with_query::<_, _, FunctionBuilder>(
+                Self::INVOKE_CLOUD_FUNCTION,
+                &self.token().await?,
+                &FunctionQuery { env, name },
+                data,
+            )
+            .await?;
+
+        event!(
+            Level::DEBUG,
+            "cloud function response: {}",
+            builder.resp_data
+        );
+
+        Ok(builder.resp_data)
+    }
+
+    #[instrument(skip(self))]
+    async fn database_add(&self, env: &str, query: &str) -> Result<Vec<String>> {
+        let builder = self
+            .client()
+            .post::<_, DatabaseAddBuilder>(
+                Self::DATABASE_ADD,
+                &self.token().await?,
+                &DatabaseRequest { env, query },
+            )
+            .await?;
+
+        Ok(builder.id_list)
+    }
+
+    #[instrument(skip(self))]
+    async fn database_query(&self, env: &str, query: &str) -> Result<DatabaseQuery> {
+        self.client()
+            .post(
+                Self::DATABASE_QUERY,
+                &self.token().await?,
+                &DatabaseRequest { env, query },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn database_update(&self, env: &str, query: &str) -> Result<DatabaseUpdate> {
+        self.client()
+            .post(
+                Self::DATABASE_UPDATE,
+                &self.token().await?,
+                &DatabaseRequest { env, query },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn database_delete(&self, env: &str, query: &str) -> Result<u64> {
+        let builder = self
+            .client()
+            .post::<_, DatabaseDeleteBuilder>(
+                Self::DATABASE_DELETE,
+                &self.token().await?,
+                &DatabaseRequest { env, query },
+            )
+            .await?;
+
+        Ok(builder.deleted)
+    }
+
+    #[instrument(skip(self))]
+    async fn database_aggregate(&self, env: &str, query: &str) -> Result<Vec<String>> {
+        let builder = self
+            .client()
+            .post::<_, DatabaseAggregateBuilder>(
+                Self::DATABASE_AGGREGATE,
+                &self.token().await?,
+                &DatabaseRequest { env, query },
+            )
+            .await?;
+
+        Ok(builder.data)
+    }
+
+    #[instrument(skip(self))]
+    async fn database_count(&self, env: &str, query: &str) -> Result<u64> {
+        let builder = self
+            .client()
+            .post::<_, DatabaseCountBuilder>(
+                Self::DATABASE_COUNT,
+                &self.token().await?,
+                &DatabaseRequest { env, query },
+            )
+            .await?;
+
+        Ok(builder.count)
+    }
+
+    #[instrument(skip(self))]
+    async fn upload_file(&self, env: &str, path: &str) -> Result<UploadFile> {
+        self.client()
+            .post(
+                Self::UPLOAD_FILE,
+                &self.token().await?,
+                &UploadFileRequest { env, path },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn batch_download_file(
+        &self,
+        env: &str,
+        files: &[DownloadFile],
+    ) -> Result<Vec<DownloadLink>> {
+        let builder = self
+            .client()
+            .post::<_, DownloadFileBuilder>(
+                Self::BATCH_DOWNLOAD_FILE,
+                &self.token().await?,
+                &DownloadFileRequest {
+                    env,
+                    file_list: files,
+                },
+            )
+            .await?;
+
+        Ok(builder.file_list)
+    }
+}
@@ -1,10 +1,4 @@
-use std::{
-    collections::HashMap,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-};
+use std::sync::Arc;
 
 use aes::{
     cipher::{block_padding::Pkcs7, generic_array::GenericArray, BlockDecryptMut, KeyIvInit},
@@ -19,13 +13,16 @@ use hmac::{Hmac, Mac};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::from_slice;
 use sha2::Sha256;
-use tokio::sync::{Notify, RwLock};
+use tokio::sync::RwLock;
 use tracing::{event, instrument, Level};
+use zeroize::Zeroizing;
 
 use crate::{
     client::Client,
-    error::Error::InternalServer,
-    response::Response,
+    error::Error::EncryptKeyNotFound,
+    id::{OpenId, SessionKey, UnionId},
+    refresh::RefreshOnce,
+    secret::Secret,
     user::{User, UserBuilder},
     Result,
 };
@@ -34,35 +31,35 @@ type Aes128CbcDec = Decryptor<Aes128>;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Credential {
-    open_id: String,
-    session_key: String,
+    open_id: OpenId,
+    session_key: SessionKey,
     #[serde(skip_serializing_if = "Option::is_none")]
-    union_id: Option<String>,
+    union_id: Option<UnionId>,
 }
 
 impl Credential {
-    pub fn open_id(&self) -> &str {
+    pub fn open_id(&self) -> &OpenId {
         &self.open_id
     }
 
-    pub fn session_key(&self) -> &str {
+    pub fn session_key(&self) -> &SessionKey {
         &self.session_key
     }
 
-    pub fn union_id(&self) -> Option<&str> {
-        self.union_id.as_deref()
+    pub fn union_id(&self) -> Option<&UnionId> {
+        self.union_id.as_ref()
     }
 
     /// 解密用户数据，使用的是 AES-128-CBC 算法，数据采用PKCS#7填充。
     /// https://developers.weixin.qq.com/miniprogram/dev/framework/open-ability/signature.html
     /// ```rust
     /// use axum::{extract::State, response::IntoResponse, Json};
-    /// use open_wechat::{client::Client, Result};
+    /// use open_wechat::{client::Client, id::LoginCode, Result};
     /// use serde::Deserialize;
     ///
-    /// #[derive(Deserialize, Default)]
+    /// #[derive(Deserialize)]
     /// pub(crate) struct EncryptedPayload {
-    ///     code: String,
+    ///     code: LoginCode,
     ///     encrypted_data: String,
     ///     iv: String,
     /// }
@@ -80,10 +77,7 @@ impl Credential {
     /// ```
     #[instrument(skip(self, encrypted_data, iv))]
     pub fn decrypt(&self, encrypted_data: &str, iv: &str) -> Result<User> {
-        event!(Level::DEBUG, "encrypted_data: {}", encrypted_data);
-        event!(Level::DEBUG, "iv: {}", iv);
-
-        let key = STANDARD.decode(self.session_key.as_bytes())?;
+        let key = Zeroizing::new(STANDARD.decode(self.session_key.as_str())?);
         let iv = STANDARD.decode(iv.as_bytes())?;
 
         let decryptor = Aes128CbcDec::new(
@@ -117,10 +111,10 @@ impl std::fmt::Debug for Credential {
 #[derive(Deserialize)]
 pub(crate) struct CredentialBuilder {
     #[serde(rename = "openid")]
-    open_id: String,
-    session_key: String,
+    open_id: OpenId,
+    session_key: SessionKey,
     #[serde(rename = "unionid")]
-    union_id: Option<String>,
+    union_id: Option<UnionId>,
 }
 
 impl CredentialBuilder {
@@ -145,8 +139,8 @@ impl std::fmt::Debug for CredentialBuilder {
 
 #[derive(Clone)]
 pub struct AccessToken {
-    access_token: String,
-    expired_at: DateTime<Utc>,
+    pub(crate) access_token: Secret,
+    pub(crate) expired_at: DateTime<Utc>,
 }
 
 impl std::fmt::Debug for AccessToken {
@@ -160,9 +154,9 @@ impl std::fmt::Debug for AccessToken {
 
 #[derive(Clone)]
 pub struct StableAccessToken {
-    access_token: String,
-    expired_at: DateTime<Utc>,
-    force_refresh: Option<bool>,
+    pub(crate) access_token: Secret,
+    pub(crate) expired_at: DateTime<Utc>,
+    pub(crate) force_refresh: Option<bool>,
 }
 
 impl std::fmt::Debug for StableAccessToken {
@@ -178,8 +172,7 @@ impl std::fmt::Debug for StableAccessToken {
 #[derive(Debug, Clone)]
 pub struct GenericAccessToken<T = AccessToken> {
     inner: Arc<RwLock<T>>,
-    refreshing: Arc<AtomicBool>,
-    notify: Arc<Notify>,
+    refresh: Arc<RefreshOnce>,
     client: Client,
 }
 
@@ -189,7 +182,7 @@ pub trait GetAccessToken {
     where
         Self: Sized;
 
-    async fn access_token(&self) -> Result<String>;
+    async fn access_token(&self) -> Result<Secret>;
 }
 
 #[async_trait]
@@ -220,53 +213,43 @@ impl GetAccessToken for GenericAccessToken<AccessToken> {
                 access_token: builder.access_token,
                 expired_at: builder.expired_at,
             })),
-            refreshing: Arc::new(AtomicBool::new(false)),
-            notify: Arc::new(Notify::new()),
+            refresh: Arc::new(RefreshOnce::default()),
             client,
         })
     }
 
-    async fn access_token(&self) -> Result<String> {
-        event!(Level::DEBUG, "read access token guard");
-
-        let guard = self.inner.read().await;
-
-        if guard.expired_at <= Utc::now() {
-            event!(Level::DEBUG, "expired at: {}", guard.expired_at);
-
-            if self.refreshing.load(Ordering::Acquire) {
-                event!(Level::DEBUG, "refreshing");
-
-                self.notify.notified().await;
-            } else {
-                event!(Level::DEBUG, "prepare to fresh");
-
-                self.refreshing.store(true, Ordering::Release);
+    async fn access_token(&self) -> Result<Secret> {
+        self.refresh
+            .get(
+                || async {
+                    event!(Level::DEBUG, "read access token guard");
 
-                drop(guard);
+                    let guard = self.inner.read().await;
 
-                event!(Level::DEBUG, "write access token guard");
+                    if guard.expired_at <= Utc::now() {
+                        event!(Level::DEBUG, "expired at: {}", guard.expired_at);
 
-                let mut guard = self.inner.write().await;
+                        return None;
+                    }
 
-                let builder = self.client.get_access_token().await?;
+                    Some(guard.access_token.clone())
+                },
+                || async {
+                    let builder = self.client.get_access_token().await?;
 
-                guard.access_token = builder.access_token;
-                guard.expired_at = builder.expired_at;
+                    event!(Level::DEBUG, "write access token guard");
 
-                self.refreshing.store(false, Ordering::Release);
+                    let mut guard = self.inner.write().await;
 
-                self.notify.notify_waiters();
+                    guard.access_token = builder.access_token;
+                    guard.expired_at = builder.expired_at;
 
-                event!(Level::DEBUG, "fresh access token: {:#?}", guard);
+                    event!(Level::DEBUG, "fresh access token: {:#?}", guard);
 
-                return Ok(guard.access_token.clone());
-            }
-        }
-
-        event!(Level::DEBUG, "access token not expired");
-
-        Ok(guard.access_token.clone())
+                    Ok(guard.access_token.clone())
+                },
+            )
+            .await
     }
 }
 
@@ -279,7 +262,7 @@ pub trait GetStableAccessToken {
     where
         Self: Sized;
 
-    async fn access_token(&self) -> Result<String>;
+    async fn access_token(&self) -> Result<Secret>;
 
     async fn set_force_refresh(&self, force_refresh: bool) -> Result<()>;
 }
@@ -318,75 +301,125 @@ impl GetStableAccessToken for GenericAccessToken<StableAccessToken> {
                 expired_at: builder.expired_at,
                 force_refresh: force_refresh.into(),
             })),
-            refreshing: Arc::new(AtomicBool::new(false)),
-            notify: Arc::new(Notify::new()),
+            refresh: Arc::new(RefreshOnce::default()),
             client,
         })
     }
 
-    async fn access_token(&self) -> Result<String> {
-        event!(Level::DEBUG, "read stable access token guard");
+    async fn access_token(&self) -> Result<Secret> {
+        self.refresh
+            .get(
+                || async {
+                    event!(Level::DEBUG, "read stable access token guard");
 
-        let guard = self.inner.read().await;
+                    let guard = self.inner.read().await;
 
-        if guard.expired_at <= Utc::now() {
-            event!(Level::DEBUG, "expired at: {}", guard.expired_at);
+                    if guard.expired_at <= Utc::now() {
+                        event!(Level::DEBUG, "expired at: {}", guard.expired_at);
 
-            if self.refreshing.load(Ordering::Acquire) {
-                event!(Level::DEBUG, "refreshing");
+                        return None;
+                    }
 
-                self.notify.notified().await;
-            } else {
-                event!(Level::DEBUG, "prepare to fresh");
+                    Some(guard.access_token.clone())
+                },
+                || async {
+                    let force_refresh = self.inner.read().await.force_refresh;
 
-                self.refreshing.store(true, Ordering::Release);
+                    let builder = self.client.get_stable_access_token(force_refresh).await?;
 
-                drop(guard);
+                    event!(Level::DEBUG, "write stable access token guard");
 
-                event!(Level::DEBUG, "write stable access token guard");
+                    let mut guard = self.inner.write().await;
 
-                let mut guard = self.inner.write().await;
+                    guard.access_token = builder.access_token;
+                    guard.expired_at = builder.expired_at;
 
-                let builder = self
-                    .client
-                    .get_stable_access_token(guard.force_refresh)
-                    .await?;
+                    event!(Level::DEBUG, "fresh stable access token: {:#?}", guard);
 
-                guard.access_token = builder.access_token;
-                guard.expired_at = builder.expired_at;
+                    Ok(guard.access_token.clone())
+                },
+            )
+            .await
+    }
 
-                self.refreshing.store(false, Ordering::Release);
+    async fn set_force_refresh(&self, force_refresh: bool) -> Result<()> {
+        let mut guard = self.inner.write().await;
 
-                self.notify.notify_waiters();
+        guard.force_refresh = Some(force_refresh);
 
-                event!(Level::DEBUG, "fresh stable access token: {:#?}", guard);
+        Ok(())
+    }
+}
 
-                return Ok(guard.access_token.clone());
-            }
-        }
+/// 提供接口调用凭据和发送请求的 [`Client`]，所有需要 access_token 的接口都基于此 trait 实现，
+/// `GenericAccessToken<AccessToken>` 和 `GenericAccessToken<StableAccessToken>` 均实现了此 trait，
+/// 也可以为自行管理的 access_token 或第三方平台代调用的 authorizer_access_token 实现此 trait
+/// ```rust
+/// use async_trait::async_trait;
+/// use open_wechat::{client::Client, credential::TokenProvider, secret::Secret, user::GetContact, Result};
+///
+/// #[derive(Debug)]
+/// struct AuthorizerAccessToken {
+///     client: Client,
+///     authorizer_access_token: Secret,
+/// }
+///
+/// #[async_trait]
+/// impl TokenProvider for AuthorizerAccessToken {
+///     fn client(&self) -> &Client {
+///         &self.client
+///     }
+///
+///     async fn token(&self) -> Result<Secret> {
+///         Ok(self.authorizer_access_token.clone())
+///     }
+/// }
+///
+/// async fn get_contact(access_token: &AuthorizerAccessToken, code: &str) -> Result<()> {
+///     let contact = access_token.get_contact(code, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    /// 发送请求使用的 [`Client`]
+    fn client(&self) -> &Client;
 
-        event!(Level::DEBUG, "stable access token not expired");
+    /// 有效的接口调用凭据
+    async fn token(&self) -> Result<Secret>;
+}
 
-        Ok(guard.access_token.clone())
+#[async_trait]
+impl TokenProvider for GenericAccessToken<AccessToken> {
+    fn client(&self) -> &Client {
+        &self.client
     }
 
-    async fn set_force_refresh(&self, force_refresh: bool) -> Result<()> {
-        let mut guard = self.inner.write().await;
+    async fn token(&self) -> Result<Secret> {
+        GetAccessToken::access_token(self).await
+    }
+}
 
-        guard.force_refresh = Some(force_refresh);
+#[async_trait]
+impl TokenProvider for GenericAccessToken<StableAccessToken> {
+    fn client(&self) -> &Client {
+        &self.client
+    }
 
-        Ok(())
+    async fn token(&self) -> Result<Secret> {
+        GetStableAccessToken::access_token(self).await
     }
 }
 
 #[derive(Deserialize)]
 pub(crate) struct AccessTokenBuilder {
-    access_token: String,
+    pub(crate) access_token: Secret,
     #[serde(
         deserialize_with = "AccessTokenBuilder::deserialize_expired_at",
         rename = "expires_in"
     )]
-    expired_at: DateTime<Utc>,
+    pub(crate) expired_at: DateTime<Utc>,
 }
 
 impl AccessTokenBuilder {
@@ -417,78 +450,49 @@ pub trait CheckSessionKey {
 
     /// 检查登录态是否过期
     /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/checkSessionKey.html
-    async fn check_session_key(&self, session_key: &str, open_id: &str) -> Result<()>;
+    async fn check_session_key(&self, session_key: &SessionKey, open_id: &OpenId) -> Result<()>;
 }
 
 type HmacSha256 = Hmac<Sha256>;
 
-#[async_trait]
-impl CheckSessionKey for GenericAccessToken<AccessToken> {
-    #[instrument(skip(self, session_key, open_id))]
-    async fn check_session_key(&self, session_key: &str, open_id: &str) -> Result<()> {
-        let mut mac = HmacSha256::new_from_slice(session_key.as_bytes())?;
-        mac.update(b"");
-        let hasher = mac.finalize();
-        let signature = encode(hasher.into_bytes());
-
-        let mut map = HashMap::new();
-
-        map.insert("openid", open_id.to_string());
-        map.insert("signature", signature);
-        map.insert("sig_method", "hmac_sha256".into());
-
-        let response = self
-            .client
-            .request()
-            .get(Self::CHECK_SESSION_KEY)
-            .query(&map)
-            .send()
-            .await?;
+/// 使用 session_key 对空字符串进行 HMAC-SHA256 签名
+fn sign_session_key(session_key: &SessionKey) -> Result<String> {
+    let mut mac = HmacSha256::new_from_slice(session_key.as_str().as_bytes())?;
+    mac.update(b"");
 
-        event!(Level::DEBUG, "response: {:#?}", response);
+    Ok(encode(mac.finalize().into_bytes()))
+}
 
-        if response.status().is_success() {
-            let response = response.json::<Response<()>>().await?;
+#[derive(Serialize)]
+pub(crate) struct SessionKeySignature<'a> {
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+    signature: String,
+    sig_method: &'static str,
+}
 
-            response.extract()
-        } else {
-            Err(crate::error::Error::InternalServer(response.text().await?))
-        }
+impl<'a> SessionKeySignature<'a> {
+    pub(crate) fn new(session_key: &SessionKey, open_id: &'a OpenId) -> Result<Self> {
+        Ok(Self {
+            open_id: open_id.as_str(),
+            signature: sign_session_key(session_key)?,
+            sig_method: "hmac_sha256",
+        })
     }
 }
 
 #[async_trait]
-impl CheckSessionKey for GenericAccessToken<StableAccessToken> {
+impl<T> CheckSessionKey for T
+where
+    T: TokenProvider,
+{
     #[instrument(skip(self, session_key, open_id))]
-    async fn check_session_key(&self, session_key: &str, open_id: &str) -> Result<()> {
-        let mut mac = HmacSha256::new_from_slice(session_key.as_bytes())?;
-        mac.update(b"");
-        let hasher = mac.finalize();
-        let signature = encode(hasher.into_bytes());
-
-        let mut map = HashMap::new();
-
-        map.insert("openid", open_id.to_string());
-        map.insert("signature", signature);
-        map.insert("sig_method", "hmac_sha256".into());
-
-        let response = self
-            .client
-            .request()
-            .get(Self::CHECK_SESSION_KEY)
-            .query(&map)
-            .send()
-            .await?;
+    async fn check_session_key(&self, session_key: &SessionKey, open_id: &OpenId) -> Result<()> {
+        let query = SessionKeySignature::new(session_key, open_id)?;
 
-        event!(Level::DEBUG, "response: {:#?}", response);
-
-        if response.status().is_success() {
-            let response = response.json::<Response<()>>().await?;
-
-            response.extract()
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+        self.client()
+            .get(Self::CHECK_SESSION_KEY, &self.token().await?, &query)
+            .await
     }
 }
 
@@ -498,85 +502,195 @@ pub trait ResetSessionKey {
 
     /// 重置用户的 session_key
     /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/ResetUserSessionKey.html
-    async fn reset_session_key(&self, session_key: &str, open_id: &str) -> Result<Credential>;
+    async fn reset_session_key(
+        &self,
+        session_key: &SessionKey,
+        open_id: &OpenId,
+    ) -> Result<Credential>;
 }
 
 #[async_trait]
-impl ResetSessionKey for GenericAccessToken<AccessToken> {
-    #[instrument(skip(self, open_id))]
-    async fn reset_session_key(&self, session_key: &str, open_id: &str) -> Result<Credential> {
-        let mut mac = HmacSha256::new_from_slice(session_key.as_bytes())?;
-        mac.update(b"");
-        let hasher = mac.finalize();
-        let signature = encode(hasher.into_bytes());
-
-        let mut map = HashMap::new();
-
-        map.insert("access_token", self.access_token().await?);
-        map.insert("openid", open_id.to_string());
-        map.insert("signature", signature);
-        map.insert("sig_method", "hmac_sha256".into());
-
-        let response = self
-            .client
-            .request()
-            .get(Self::RESET_SESSION_KEY)
-            .query(&map)
-            .send()
+impl<T> ResetSessionKey for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, session_key, open_id))]
+    async fn reset_session_key(
+        &self,
+        session_key: &SessionKey,
+        open_id: &OpenId,
+    ) -> Result<Credential> {
+        let query = SessionKeySignature::new(session_key, open_id)?;
+
+        let builder = self
+            .client()
+            .get::<_, CredentialBuilder>(Self::RESET_SESSION_KEY, &self.token().await?, &query)
             .await?;
 
-        event!(Level::DEBUG, "response: {:#?}", response);
+        let credential = builder.build();
 
-        if response.status().is_success() {
-            let response = response.json::<Response<CredentialBuilder>>().await?;
+        event!(Level::DEBUG, "credential: {:#?}", credential);
 
-            let credential = response.extract()?.build();
+        Ok(credential)
+    }
+}
 
-            event!(Level::DEBUG, "credential: {:#?}", credential);
+/// 用户加密 key，用于解密 `wx.getUserCryptoManager` 在小程序端加密的数据
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UserEncryptKey {
+    encrypt_key: Secret,
+    version: u32,
+    expire_in: i64,
+    iv: Secret,
+    create_time: i64,
+}
 
-            Ok(credential)
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+impl UserEncryptKey {
+    /// base64 编码的加密 key
+    pub fn encrypt_key(&self) -> &str {
+        self.encrypt_key.expose()
+    }
+
+    /// key 的版本号，与小程序端 `getLatestUserKey` 返回的 version 对应
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// 剩余有效时间，单位为秒
+    pub fn expire_in(&self) -> i64 {
+        self.expire_in
+    }
+
+    pub fn iv(&self) -> &str {
+        self.iv.expose()
+    }
+
+    pub fn create_time(&self) -> i64 {
+        self.create_time
+    }
+
+    /// 解密小程序端使用此 key 加密的数据，使用的是 AES-128-CBC 算法，数据采用 PKCS#7 填充，
+    /// `encrypted_data` 为 base64 编码的密文
+    #[instrument(skip(self, encrypted_data))]
+    pub fn decrypt(&self, encrypted_data: &str) -> Result<Vec<u8>> {
+        let key = Zeroizing::new(STANDARD.decode(self.encrypt_key.expose())?);
+
+        let decryptor = Aes128CbcDec::new_from_slices(&key, self.iv.expose().as_bytes())?;
+
+        let encrypted_data = STANDARD.decode(encrypted_data.as_bytes())?;
+
+        Ok(decryptor.decrypt_padded_vec_mut::<Pkcs7>(&encrypted_data)?)
     }
 }
 
-#[async_trait]
-impl ResetSessionKey for GenericAccessToken<StableAccessToken> {
-    #[instrument(skip(self, open_id))]
-    async fn reset_session_key(&self, session_key: &str, open_id: &str) -> Result<Credential> {
-        let mut mac = HmacSha256::new_from_slice(session_key.as_bytes())?;
-        mac.update(b"");
-        let hasher = mac.finalize();
-        let signature = encode(hasher.into_bytes());
-
-        let mut map = HashMap::new();
-
-        map.insert("access_token", self.access_token().await?);
-        map.insert("openid", open_id.to_string());
-        map.insert("signature", signature);
-        map.insert("sig_method", "hmac_sha256".into());
-
-        let response = self
-            .client
-            .request()
-            .get(Self::RESET_SESSION_KEY)
-            .query(&map)
-            .send()
-            .await?;
+impl std::fmt::Debug for UserEncryptKey {
+    // 为了安全，不打印 encrypt_key 和 iv
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserEncryptKey")
+            .field("encrypt_key", &"********")
+            .field("version", &self.version)
+            .field("expire_in", &self.expire_in)
+            .field("iv", &"********")
+            .field("create_time", &self.create_time)
+            .finish()
+    }
+}
 
-        event!(Level::DEBUG, "response: {:#?}", response);
+/// 用户最近三次的加密 key
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserEncryptKeys {
+    #[serde(default)]
+    key_info_list: Vec<UserEncryptKey>,
+}
 
-        if response.status().is_success() {
-            let response = response.json::<Response<CredentialBuilder>>().await?;
+impl UserEncryptKeys {
+    pub fn keys(&self) -> &[UserEncryptKey] {
+        &self.key_info_list
+    }
 
-            let credential = response.extract()?.build();
+    /// 版本号最大的 key
+    pub fn latest(&self) -> Option<&UserEncryptKey> {
+        self.key_info_list.iter().max_by_key(|key| key.version)
+    }
 
-            event!(Level::DEBUG, "credential: {:#?}", credential);
+    pub fn get(&self, version: u32) -> Option<&UserEncryptKey> {
+        self.key_info_list.iter().find(|key| key.version == version)
+    }
 
-            Ok(credential)
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+    /// 使用对应版本的 key 解密小程序端加密的数据
+    /// ```rust
+    /// use axum::{extract::State, response::IntoResponse, Json};
+    /// use open_wechat::{
+    ///     client::Client,
+    ///     credential::{GenericAccessToken, GetUserEncryptKey},
+    ///     id::LoginCode,
+    ///     Result,
+    /// };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// pub(crate) struct EncryptedPayload {
+    ///     code: LoginCode,
+    ///     version: u32,
+    ///     encrypted_data: String,
+    /// }
+    ///
+    /// pub(crate) async fn decrypt(
+    ///     State((client, access_token)): State<(Client, GenericAccessToken)>,
+    ///     Json(payload): Json<EncryptedPayload>,
+    /// ) -> Result<impl IntoResponse> {
+    ///     let credential = client.login(&payload.code).await?;
+    ///
+    ///     let keys = access_token
+    ///         .get_user_encrypt_key(credential.session_key(), credential.open_id())
+    ///         .await?;
+    ///
+    ///     let data = keys.decrypt(payload.version, &payload.encrypted_data)?;
+    ///
+    ///     Ok(data)
+    /// }
+    /// ```
+    pub fn decrypt(&self, version: u32, encrypted_data: &str) -> Result<Vec<u8>> {
+        self.get(version)
+            .ok_or(EncryptKeyNotFound(version))?
+            .decrypt(encrypted_data)
+    }
+}
+
+#[async_trait]
+pub trait GetUserEncryptKey {
+    const USER_ENCRYPT_KEY: &'static str =
+        "https://api.weixin.qq.com/wxa/business/getuserencryptkey";
+
+    /// 获取用户最近三次的加密 key，每个 key 的有效期为两天
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/internet/getUserEncryptKey.html
+    async fn get_user_encrypt_key(
+        &self,
+        session_key: &SessionKey,
+        open_id: &OpenId,
+    ) -> Result<UserEncryptKeys>;
+}
+
+#[async_trait]
+impl<T> GetUserEncryptKey for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, session_key, open_id))]
+    async fn get_user_encrypt_key(
+        &self,
+        session_key: &SessionKey,
+        open_id: &OpenId,
+    ) -> Result<UserEncryptKeys> {
+        let query = SessionKeySignature::new(session_key, open_id)?;
+
+        let keys = self
+            .client()
+            .post_query::<_, UserEncryptKeys>(Self::USER_ENCRYPT_KEY, &self.token().await?, &query)
+            .await?;
+
+        event!(Level::DEBUG, "user encrypt keys: {:#?}", keys);
+
+        Ok(keys)
     }
 }
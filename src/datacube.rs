@@ -0,0 +1,944 @@
+use async_trait::async_trait;
+use chrono::{Datelike, FixedOffset, Months, NaiveDate, NaiveTime, Weekday};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use serde_json::from_str;
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, error::Error::InvalidDateRange, Result};
+
+/// 查询的日期范围，包含开始日期和结束日期
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    begin: NaiveDate,
+    end: NaiveDate,
+}
+
+impl DateRange {
+    /// 开始日期不能晚于结束日期
+    pub fn new(begin: NaiveDate, end: NaiveDate) -> Result<Self> {
+        if begin > end {
+            return Err(InvalidDateRange(format!(
+                "begin date {} is after end date {}",
+                begin, end
+            )));
+        }
+
+        Ok(Self { begin, end })
+    }
+
+    /// 单日
+    pub fn day(date: NaiveDate) -> Self {
+        Self {
+            begin: date,
+            end: date,
+        }
+    }
+
+    /// `date` 所在的自然周，周一至周日
+    pub fn week(date: NaiveDate) -> Self {
+        let week = date.week(Weekday::Mon);
+
+        Self {
+            begin: week.first_day(),
+            end: week.last_day(),
+        }
+    }
+
+    /// 自然月，月初至月末
+    pub fn month(year: i32, month: u32) -> Result<Self> {
+        let invalid = || InvalidDateRange(format!("invalid month {}-{}", year, month));
+
+        let begin = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(invalid)?;
+
+        let end = begin
+            .checked_add_months(Months::new(1))
+            .and_then(|date| date.pred_opt())
+            .ok_or_else(invalid)?;
+
+        Ok(Self { begin, end })
+    }
+
+    pub fn begin(&self) -> NaiveDate {
+        self.begin
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        self.end
+    }
+
+    /// 包含的天数
+    pub fn days(&self) -> i64 {
+        (self.end - self.begin).num_days() + 1
+    }
+
+    fn ensure_day(&self) -> Result<()> {
+        if self.begin != self.end {
+            return Err(InvalidDateRange(format!(
+                "{} to {} is not a single day",
+                self.begin, self.end
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn ensure_week(&self) -> Result<()> {
+        if *self != Self::week(self.begin) {
+            return Err(InvalidDateRange(format!(
+                "{} to {} is not a natural week from monday to sunday",
+                self.begin, self.end
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn ensure_month(&self) -> Result<()> {
+        if Self::month(self.begin.year(), self.begin.month()).ok() != Some(*self) {
+            return Err(InvalidDateRange(format!(
+                "{} to {} is not a natural month",
+                self.begin, self.end
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn ensure_days(&self, allowed: &[i64]) -> Result<()> {
+        if !allowed.contains(&self.days()) {
+            return Err(InvalidDateRange(format!(
+                "{} to {} spans {} days, expected one of {:?}",
+                self.begin,
+                self.end,
+                self.days(),
+                allowed
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 解析微信返回的 `ref_date`，格式为 `20170313`、`20170306-20170312` 或 `201703`
+    fn parse(ref_date: &str) -> Option<Self> {
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y%m%d").ok();
+
+        match ref_date.split_once('-') {
+            Some((begin, end)) => Self::new(date(begin)?, date(end)?).ok(),
+            None if ref_date.len() == 6 => {
+                let year = ref_date.get(..4)?.parse().ok()?;
+                let month = ref_date.get(4..)?.parse().ok()?;
+
+                Self::month(year, month).ok()
+            }
+            None => date(ref_date).map(Self::day),
+        }
+    }
+
+    fn deserialize_ref_date<'de, D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ref_date = String::deserialize(deserializer)?;
+
+        Self::parse(&ref_date)
+            .ok_or_else(|| D::Error::custom(format!("invalid ref_date {}", ref_date)))
+    }
+}
+
+#[derive(Serialize)]
+struct DateRangeRequest {
+    begin_date: String,
+    end_date: String,
+}
+
+impl From<&DateRange> for DateRangeRequest {
+    fn from(range: &DateRange) -> Self {
+        Self {
+            begin_date: range.begin.format("%Y%m%d").to_string(),
+            end_date: range.end.format("%Y%m%d").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListBuilder<T> {
+    list: Vec<T>,
+}
+
+/// 用户访问小程序数据概况
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailySummary {
+    #[serde(deserialize_with = "DateRange::deserialize_ref_date")]
+    ref_date: DateRange,
+    visit_total: u64,
+    share_pv: u64,
+    share_uv: u64,
+}
+
+impl DailySummary {
+    pub fn ref_date(&self) -> DateRange {
+        self.ref_date
+    }
+
+    /// 累计用户数
+    pub fn visit_total(&self) -> u64 {
+        self.visit_total
+    }
+
+    /// 转发次数
+    pub fn share_pv(&self) -> u64 {
+        self.share_pv
+    }
+
+    /// 转发人数
+    pub fn share_uv(&self) -> u64 {
+        self.share_uv
+    }
+}
+
+/// 用户访问小程序数据趋势，日趋势、周趋势和月趋势的字段相同
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VisitTrend {
+    #[serde(deserialize_with = "DateRange::deserialize_ref_date")]
+    ref_date: DateRange,
+    session_cnt: u64,
+    visit_pv: u64,
+    visit_uv: u64,
+    visit_uv_new: u64,
+    stay_time_uv: f64,
+    stay_time_session: f64,
+    visit_depth: f64,
+}
+
+impl VisitTrend {
+    pub fn ref_date(&self) -> DateRange {
+        self.ref_date
+    }
+
+    /// 打开次数
+    pub fn session_cnt(&self) -> u64 {
+        self.session_cnt
+    }
+
+    /// 访问次数
+    pub fn visit_pv(&self) -> u64 {
+        self.visit_pv
+    }
+
+    /// 访问人数
+    pub fn visit_uv(&self) -> u64 {
+        self.visit_uv
+    }
+
+    /// 新用户数
+    pub fn visit_uv_new(&self) -> u64 {
+        self.visit_uv_new
+    }
+
+    /// 人均停留时长，单位为秒
+    pub fn stay_time_uv(&self) -> f64 {
+        self.stay_time_uv
+    }
+
+    /// 次均停留时长，单位为秒
+    pub fn stay_time_session(&self) -> f64 {
+        self.stay_time_session
+    }
+
+    /// 平均访问深度
+    pub fn visit_depth(&self) -> f64 {
+        self.visit_depth
+    }
+}
+
+/// 用户访问小程序留存
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetainInfo {
+    #[serde(deserialize_with = "DateRange::deserialize_ref_date")]
+    ref_date: DateRange,
+    visit_uv_new: Vec<Retain>,
+    visit_uv: Vec<Retain>,
+}
+
+impl RetainInfo {
+    pub fn ref_date(&self) -> DateRange {
+        self.ref_date
+    }
+
+    /// 新增用户留存
+    pub fn visit_uv_new(&self) -> &[Retain] {
+        &self.visit_uv_new
+    }
+
+    /// 活跃用户留存
+    pub fn visit_uv(&self) -> &[Retain] {
+        &self.visit_uv
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Retain {
+    key: u32,
+    value: u64,
+}
+
+impl Retain {
+    /// 标识，0 开始，0 表示当天（周、月），1 表示 1 天（周、月）后，依此类推
+    pub fn key(&self) -> u32 {
+        self.key
+    }
+
+    /// key 对应日期的新增用户数或活跃用户数
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// 小程序新增或活跃用户的画像分布数据
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserPortrait {
+    #[serde(deserialize_with = "DateRange::deserialize_ref_date")]
+    ref_date: DateRange,
+    visit_uv_new: Portrait,
+    visit_uv: Portrait,
+}
+
+impl UserPortrait {
+    pub fn ref_date(&self) -> DateRange {
+        self.ref_date
+    }
+
+    /// 新用户画像
+    pub fn visit_uv_new(&self) -> &Portrait {
+        &self.visit_uv_new
+    }
+
+    /// 活跃用户画像
+    pub fn visit_uv(&self) -> &Portrait {
+        &self.visit_uv
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Portrait {
+    #[serde(default)]
+    province: Vec<PortraitItem>,
+    #[serde(default)]
+    city: Vec<PortraitItem>,
+    #[serde(default)]
+    genders: Vec<PortraitItem>,
+    #[serde(default)]
+    platforms: Vec<PortraitItem>,
+    #[serde(default)]
+    devices: Vec<PortraitItem>,
+    #[serde(default)]
+    ages: Vec<PortraitItem>,
+}
+
+impl Portrait {
+    /// 省份，如北京、广东等
+    pub fn province(&self) -> &[PortraitItem] {
+        &self.province
+    }
+
+    /// 城市，如北京、广州等
+    pub fn city(&self) -> &[PortraitItem] {
+        &self.city
+    }
+
+    /// 性别，包括男、女、未知
+    pub fn genders(&self) -> &[PortraitItem] {
+        &self.genders
+    }
+
+    /// 终端类型，包括 iPhone、android、其他
+    pub fn platforms(&self) -> &[PortraitItem] {
+        &self.platforms
+    }
+
+    /// 机型，如苹果 iPhone 6、OPPO R9 等
+    pub fn devices(&self) -> &[PortraitItem] {
+        &self.devices
+    }
+
+    /// 年龄，包括 17 岁以下、18-24 岁等区间
+    pub fn ages(&self) -> &[PortraitItem] {
+        &self.ages
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PortraitItem {
+    #[serde(default)]
+    id: Option<i64>,
+    name: String,
+    value: u64,
+}
+
+impl PortraitItem {
+    /// 属性值 id，机型分布没有 id
+    pub fn id(&self) -> Option<i64> {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 该场景的用户数
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// 用户小程序访问分布数据
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VisitDistribution {
+    #[serde(deserialize_with = "DateRange::deserialize_ref_date")]
+    ref_date: DateRange,
+    list: Vec<Distribution>,
+}
+
+impl VisitDistribution {
+    pub fn ref_date(&self) -> DateRange {
+        self.ref_date
+    }
+
+    pub fn list(&self) -> &[Distribution] {
+        &self.list
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Distribution {
+    index: String,
+    item_list: Vec<DistributionItem>,
+}
+
+impl Distribution {
+    /// 分布类型，如 `access_source_session_cnt`、`access_staytime_info`、`access_depth_info`
+    pub fn index(&self) -> &str {
+        &self.index
+    }
+
+    pub fn item_list(&self) -> &[DistributionItem] {
+        &self.item_list
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DistributionItem {
+    key: u32,
+    value: u64,
+    #[serde(default)]
+    access_source_visit_uv: Option<u64>,
+}
+
+impl DistributionItem {
+    /// 场景 id，含义见微信文档中各分布类型的枚举值
+    pub fn key(&self) -> u32 {
+        self.key
+    }
+
+    /// 该场景 id 访问 pv
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// 该场景 id 访问 uv，仅访问来源分布返回
+    pub fn access_source_visit_uv(&self) -> Option<u64> {
+        self.access_source_visit_uv
+    }
+}
+
+/// 访问页面数据，只返回访问量排名前 200 的页面
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VisitPage {
+    #[serde(deserialize_with = "DateRange::deserialize_ref_date")]
+    ref_date: DateRange,
+    list: Vec<PageVisit>,
+}
+
+impl VisitPage {
+    pub fn ref_date(&self) -> DateRange {
+        self.ref_date
+    }
+
+    pub fn list(&self) -> &[PageVisit] {
+        &self.list
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PageVisit {
+    page_path: String,
+    page_visit_pv: u64,
+    page_visit_uv: u64,
+    page_staytime_pv: f64,
+    entrypage_pv: u64,
+    exitpage_pv: u64,
+    page_share_pv: u64,
+    page_share_uv: u64,
+}
+
+impl PageVisit {
+    pub fn page_path(&self) -> &str {
+        &self.page_path
+    }
+
+    /// 访问次数
+    pub fn page_visit_pv(&self) -> u64 {
+        self.page_visit_pv
+    }
+
+    /// 访问人数
+    pub fn page_visit_uv(&self) -> u64 {
+        self.page_visit_uv
+    }
+
+    /// 次均停留时长，单位为秒
+    pub fn page_staytime_pv(&self) -> f64 {
+        self.page_staytime_pv
+    }
+
+    /// 进入页次数
+    pub fn entrypage_pv(&self) -> u64 {
+        self.entrypage_pv
+    }
+
+    /// 退出页次数
+    pub fn exitpage_pv(&self) -> u64 {
+        self.exitpage_pv
+    }
+
+    /// 转发次数
+    pub fn page_share_pv(&self) -> u64 {
+        self.page_share_pv
+    }
+
+    /// 转发人数
+    pub fn page_share_uv(&self) -> u64 {
+        self.page_share_uv
+    }
+}
+
+/// 小程序性能数据查询参数
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PerformanceQuery {
+    /// 查询数据的类型，如 `10016` 打开率、`10017` 启动各阶段耗时、`10021` 页面切换耗时、
+    /// `10022` 内存指标、`10023` 内存异常
+    pub module: String,
+    /// 查询条件，如网络类型、机型档位、平台等
+    pub params: Vec<PerformanceParam>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PerformanceParam {
+    /// 查询条件，如 `networktype`、`device_level`、`device`
+    pub field: String,
+    /// 查询条件值，如 `wifi`、`-1`
+    pub value: String,
+}
+
+#[derive(Serialize)]
+struct PerformanceRequest<'a> {
+    time: PerformanceTime,
+    #[serde(flatten)]
+    query: &'a PerformanceQuery,
+}
+
+#[derive(Serialize)]
+struct PerformanceTime {
+    begin_timestamp: i64,
+    end_timestamp: i64,
+}
+
+impl From<&DateRange> for PerformanceTime {
+    // 按北京时间计算开始日期的零点和结束日期的最后一秒
+    fn from(range: &DateRange) -> Self {
+        let offset = FixedOffset::east_opt(8 * 60 * 60).unwrap();
+
+        let timestamp = |date: NaiveDate, time: NaiveTime| {
+            date.and_time(time)
+                .and_local_timezone(offset)
+                .single()
+                .map(|datetime| datetime.timestamp())
+                .unwrap_or_default()
+        };
+
+        Self {
+            begin_timestamp: timestamp(range.begin, NaiveTime::MIN),
+            end_timestamp: timestamp(range.end, NaiveTime::from_hms_opt(23, 59, 59).unwrap()),
+        }
+    }
+}
+
+// `data` 是 JSON 字符串，需要再次解析
+#[derive(Debug, Deserialize)]
+struct PerformanceBuilder {
+    data: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PerformanceBody {
+    body: Performance,
+}
+
+/// 小程序性能数据
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Performance {
+    #[serde(default)]
+    tables: Vec<PerformanceTable>,
+    #[serde(default)]
+    count: u64,
+}
+
+impl Performance {
+    pub fn tables(&self) -> &[PerformanceTable] {
+        &self.tables
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerformanceTable {
+    id: String,
+    #[serde(default)]
+    zh: String,
+    #[serde(default)]
+    lines: Vec<PerformanceLine>,
+}
+
+impl PerformanceTable {
+    /// 性能数据指标 id
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 性能数据指标中文名
+    pub fn zh(&self) -> &str {
+        &self.zh
+    }
+
+    pub fn lines(&self) -> &[PerformanceLine] {
+        &self.lines
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerformanceLine {
+    fields: Vec<PerformanceField>,
+}
+
+impl PerformanceLine {
+    pub fn fields(&self) -> &[PerformanceField] {
+        &self.fields
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerformanceField {
+    #[serde(
+        rename(deserialize = "refdate"),
+        deserialize_with = "PerformanceField::deserialize_ref_date"
+    )]
+    ref_date: NaiveDate,
+    value: String,
+}
+
+impl PerformanceField {
+    pub fn ref_date(&self) -> NaiveDate {
+        self.ref_date
+    }
+
+    /// 性能数据值，微信以字符串返回
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn deserialize_ref_date<'de, D>(deserializer: D) -> std::result::Result<NaiveDate, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ref_date = String::deserialize(deserializer)?;
+
+        NaiveDate::parse_from_str(&ref_date, "%Y%m%d").map_err(D::Error::custom)
+    }
+}
+
+/// 数据分析接口，只能查询昨天及以前的数据
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use chrono::NaiveDate;
+/// use open_wechat::{
+///     credential::GenericAccessToken,
+///     datacube::{AnalyzeData, DateRange},
+///     Result,
+/// };
+/// use serde_json::json;
+///
+/// pub(crate) async fn weekly_visit_trend(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+///
+///     let trends = access_token
+///         .get_weekly_visit_trend(&DateRange::week(date))
+///         .await?;
+///
+///     let retain = access_token
+///         .get_monthly_retain(&DateRange::month(2024, 12)?)
+///         .await?;
+///
+///     Ok(Json(json!({
+///         "trends": trends,
+///         "retain": retain,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait AnalyzeData {
+    const DAILY_SUMMARY: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappiddailysummarytrend";
+    const DAILY_VISIT_TREND: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappiddailyvisittrend";
+    const WEEKLY_VISIT_TREND: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappidweeklyvisittrend";
+    const MONTHLY_VISIT_TREND: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappidmonthlyvisittrend";
+    const DAILY_RETAIN: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappiddailyretaininfo";
+    const WEEKLY_RETAIN: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappidweeklyretaininfo";
+    const MONTHLY_RETAIN: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappidmonthlyretaininfo";
+    const USER_PORTRAIT: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappiduserportrait";
+    const VISIT_DISTRIBUTION: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappidvisitdistribution";
+    const VISIT_PAGE: &'static str =
+        "https://api.weixin.qq.com/datacube/getweanalysisappidvisitpage";
+    const PERFORMANCE: &'static str = "https://api.weixin.qq.com/wxa/business/performance/boot";
+
+    /// 获取用户访问小程序数据概况，`range` 只能是单日
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/others/getDailySummary.html
+    async fn get_daily_summary(&self, range: &DateRange) -> Result<Vec<DailySummary>>;
+
+    /// 获取用户访问小程序数据日趋势，`range` 只能是单日
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/visit-trend/getDailyVisitTrend.html
+    async fn get_daily_visit_trend(&self, range: &DateRange) -> Result<Vec<VisitTrend>>;
+
+    /// 获取用户访问小程序数据周趋势，`range` 只能是自然周
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/visit-trend/getWeeklyVisitTrend.html
+    async fn get_weekly_visit_trend(&self, range: &DateRange) -> Result<Vec<VisitTrend>>;
+
+    /// 获取用户访问小程序数据月趋势，`range` 只能是自然月
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/visit-trend/getMonthlyVisitTrend.html
+    async fn get_monthly_visit_trend(&self, range: &DateRange) -> Result<Vec<VisitTrend>>;
+
+    /// 获取用户访问小程序日留存，`range` 只能是单日
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/visit-retain/getDailyRetain.html
+    async fn get_daily_retain(&self, range: &DateRange) -> Result<RetainInfo>;
+
+    /// 获取用户访问小程序周留存，`range` 只能是自然周
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/visit-retain/getWeeklyRetain.html
+    async fn get_weekly_retain(&self, range: &DateRange) -> Result<RetainInfo>;
+
+    /// 获取用户访问小程序月留存，`range` 只能是自然月
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/visit-retain/getMonthlyRetain.html
+    async fn get_monthly_retain(&self, range: &DateRange) -> Result<RetainInfo>;
+
+    /// 获取小程序新增或活跃用户的画像分布数据，`range` 只能是 1 天、7 天或 30 天
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/others/getUserPortrait.html
+    async fn get_user_portrait(&self, range: &DateRange) -> Result<UserPortrait>;
+
+    /// 获取用户小程序访问分布数据，`range` 只能是单日
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/others/getVisitDistribution.html
+    async fn get_visit_distribution(&self, range: &DateRange) -> Result<VisitDistribution>;
+
+    /// 获取访问页面数据，`range` 只能是单日
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/others/getVisitPage.html
+    async fn get_visit_page(&self, range: &DateRange) -> Result<VisitPage>;
+
+    /// 获取小程序启动性能、运行性能等数据，`range` 按北京时间换算为时间戳
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/data-analysis/others/getPerformanceData.html
+    async fn get_performance_data(
+        &self,
+        range: &DateRange,
+        query: &PerformanceQuery,
+    ) -> Result<Performance>;
+}
+
+#[async_trait]
+impl<T> AnalyzeData for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self))]
+    async fn get_daily_summary(&self, range: &DateRange) -> Result<Vec<DailySummary>> {
+        range.ensure_day()?;
+
+        let builder = self
+            .client()
+            .post::<_, ListBuilder<DailySummary>>(
+                Self::DAILY_SUMMARY,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await?;
+
+        Ok(builder.list)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_daily_visit_trend(&self, range: &DateRange) -> Result<Vec<VisitTrend>> {
+        range.ensure_day()?;
+
+        let builder = self
+            .client()
+            .post::<_, ListBuilder<VisitTrend>>(
+                Self::DAILY_VISIT_TREND,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await?;
+
+        Ok(builder.list)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_weekly_visit_trend(&self, range: &DateRange) -> Result<Vec<VisitTrend>> {
+        range.ensure_week()?;
+
+        let builder = self
+            .client()
+            .post::<_, ListBuilder<VisitTrend>>(
+                Self::WEEKLY_VISIT_TREND,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await?;
+
+        Ok(builder.list)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_monthly_visit_trend(&self, range: &DateRange) -> Result<Vec<VisitTrend>> {
+        range.ensure_month()?;
+
+        let builder = self
+            .client()
+            .post::<_, ListBuilder<VisitTrend>>(
+                Self::MONTHLY_VISIT_TREND,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await?;
+
+        Ok(builder.list)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_daily_retain(&self, range: &DateRange) -> Result<RetainInfo> {
+        range.ensure_day()?;
+
+        self.client()
+            .post(
+                Self::DAILY_RETAIN,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_weekly_retain(&self, range: &DateRange) -> Result<RetainInfo> {
+        range.ensure_week()?;
+
+        self.client()
+            .post(
+                Self::WEEKLY_RETAIN,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_monthly_retain(&self, range: &DateRange) -> Result<RetainInfo> {
+        range.ensure_month()?;
+
+        self.client()
+            .post(
+                Self::MONTHLY_RETAIN,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user_portrait(&self, range: &DateRange) -> Result<UserPortrait> {
+        range.ensure_days(&[1, 7, 30])?;
+
+        self.client()
+            .post(
+                Self::USER_PORTRAIT,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_visit_distribution(&self, range: &DateRange) -> Result<VisitDistribution> {
+        range.ensure_day()?;
+
+        self.client()
+            .post(
+                Self::VISIT_DISTRIBUTION,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_visit_page(&self, range: &DateRange) -> Result<VisitPage> {
+        range.ensure_day()?;
+
+        self.client()
+            .post(
+                Self::VISIT_PAGE,
+                &self.token().await?,
+                &DateRangeRequest::from(range),
+            )
+            .await
+    }
+
+    #[instrument(skip(self, query))]
+    async fn get_performance_data(
+        &self,
+        range: &DateRange,
+        query: &PerformanceQuery,
+    ) -> Result<Performance> {
+        let request = PerformanceRequest {
+            time: PerformanceTime::from(range),
+            query,
+        };
+
+        let builder = self
+            .client()
+            .post::<_, PerformanceBuilder>(Self::PERFORMANCE, &self.token().await?, &request)
+            .await?;
+
+        let performance = from_str::<PerformanceBody>(&builder.data)?.body;
+
+        event!(Level::DEBUG, "performance: {:#?}", performance);
+
+        Ok(performance)
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, id::OpenId, Result};
+
+/// 跳转小程序的类型
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MiniprogramState {
+    /// 开发版
+    Developer,
+    /// 体验版
+    Trial,
+    /// 正式版
+    #[default]
+    Formal,
+}
+
+/// 进入小程序查看的语言类型
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    #[serde(rename = "zh_CN")]
+    ZhCn,
+    #[serde(rename = "en_US")]
+    EnUs,
+    #[serde(rename = "zh_HK")]
+    ZhHk,
+    #[serde(rename = "zh_TW")]
+    ZhTw,
+}
+
+/// 模板内容的值
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct MessageValue {
+    pub value: String,
+}
+
+impl From<&str> for MessageValue {
+    fn from(value: &str) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+impl From<String> for MessageValue {
+    fn from(value: String) -> Self {
+        Self { value }
+    }
+}
+
+/// 设备订阅消息
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct DeviceMessage {
+    /// 接收者（用户）的 openid 列表
+    #[serde(rename = "to_openid_list")]
+    pub to_open_id_list: Vec<OpenId>,
+    /// 点击模板卡片后的跳转页面，仅限本小程序内的页面
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+    /// 所需下发的订阅模板 id
+    pub template_id: String,
+    /// 设备唯一序列号
+    pub sn: String,
+    /// 设备型号 id
+    pub model_id: String,
+    /// 模板内容，格式形如 `{ "key1": { "value": "any" } }`
+    pub data: HashMap<String, MessageValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub miniprogram_state: Option<MiniprogramState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<Lang>,
+}
+
+#[derive(Serialize)]
+struct CreateGroupRequest<'a> {
+    model_id: &'a str,
+    group_name: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct GroupIdBuilder {
+    group_id: String,
+}
+
+#[derive(Serialize)]
+struct GroupRequest<'a> {
+    group_id: &'a str,
+}
+
+/// 设备组信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IotGroup {
+    group_name: String,
+    #[serde(default)]
+    device_list: Vec<IotDevice>,
+}
+
+impl IotGroup {
+    pub fn group_name(&self) -> &str {
+        &self.group_name
+    }
+
+    pub fn device_list(&self) -> &[IotDevice] {
+        &self.device_list
+    }
+}
+
+/// 设备组中的设备
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IotDevice {
+    #[serde(default)]
+    model_type: String,
+    model_id: String,
+    sn: String,
+}
+
+impl IotDevice {
+    /// 设备类型
+    pub fn model_type(&self) -> &str {
+        &self.model_type
+    }
+
+    /// 设备型号 id
+    pub fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    /// 设备唯一序列号
+    pub fn sn(&self) -> &str {
+        &self.sn
+    }
+}
+
+/// 硬件设备消息接口
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{
+///     client::Client,
+///     credential::GenericAccessToken,
+///     device::{DeviceMessage, ManageDevice},
+///     id::LoginCode,
+///     Result,
+/// };
+///
+/// pub(crate) async fn notify(
+///     State((client, access_token)): State<(Client, GenericAccessToken)>,
+///     Json(code): Json<LoginCode>,
+/// ) -> Result<impl IntoResponse> {
+///     let credential = client.login(&code).await?;
+///
+///     let message = DeviceMessage {
+///         to_open_id_list: vec![credential.open_id().clone()],
+///         template_id: "template_id".into(),
+///         sn: "sn".into(),
+///         model_id: "model_id".into(),
+///         data: [("thing2".to_string(), "门铃响了".into())].into(),
+///         ..Default::default()
+///     };
+///
+///     access_token.send_device_message(&message).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[async_trait]
+pub trait ManageDevice {
+    const SEND: &'static str = "https://api.weixin.qq.com/cgi-bin/message/device/subscribe/send";
+    const CREATE_GROUP_ID: &'static str = "https://api.weixin.qq.com/wxa/business/group/createid";
+    const GROUP_INFO: &'static str = "https://api.weixin.qq.com/wxa/business/group/getinfo";
+
+    /// 发送设备消息
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/hardware-device/sendHardwareDeviceMessage.html
+    async fn send_device_message(&self, message: &DeviceMessage) -> Result<()>;
+
+    /// 创建设备组，返回设备组的 group_id
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/hardware-device/createIotGroupId.html
+    async fn create_iot_group_id(&self, model_id: &str, group_name: &str) -> Result<String>;
+
+    /// 查询设备组信息
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/hardware-device/getIotGroupInfo.html
+    async fn get_iot_group_info(&self, group_id: &str) -> Result<IotGroup>;
+}
+
+#[async_trait]
+impl<T> ManageDevice for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, message))]
+    async fn send_device_message(&self, message: &DeviceMessage) -> Result<()> {
+        self.client()
+            .post(Self::SEND, &self.token().await?, message)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn create_iot_group_id(&self, model_id: &str, group_name: &str) -> Result<String> {
+        let builder = self
+            .client()
+            .post::<_, GroupIdBuilder>(
+                Self::CREATE_GROUP_ID,
+                &self.token().await?,
+                &CreateGroupRequest {
+                    model_id,
+                    group_name,
+                },
+            )
+            .await?;
+
+        event!(Level::DEBUG, "group id: {}", builder.group_id);
+
+        Ok(builder.group_id)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_iot_group_info(&self, group_id: &str) -> Result<IotGroup> {
+        self.client()
+            .post(
+                Self::GROUP_INFO,
+                &self.token().await?,
+                &GroupRequest { group_id },
+            )
+            .await
+    }
+}
@@ -1,11 +1,21 @@
+#[cfg(feature = "pay")]
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 use aes::cipher::block_padding::UnpadError;
 use aes::cipher::InvalidLength as AesInvalidLength;
+#[cfg(feature = "pay")]
+use aes_gcm::Error as AesGcmError;
 use base64::DecodeError as Base64DecodeError;
+#[cfg(feature = "reqwest")]
 use reqwest::Error as ReqwestError;
+#[cfg(feature = "pay")]
+use rsa::pkcs8::{spki::Error as SpkiError, Error as Pkcs8Error};
 use serde_json::Error as SerdeJsonError;
+use serde_urlencoded::ser::Error as UrlEncodedError;
 use strum::Display;
+#[cfg(feature = "pay")]
+use x509_cert::der::Error as DerError;
 
 #[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
@@ -20,12 +30,16 @@ pub enum Error {
     InvalidAppId(String),
     #[error("invalid code: {0}")]
     InvalidCode(String),
+    #[error("invalid refresh token: {0}")]
+    InvalidRefreshToken(String),
     #[error("invalid parameter: {0}")]
     InvalidParameter(String),
     #[error("invalid secret: {0}")]
     InvalidSecret(String),
     #[error("forbidden ip: {0}")]
     ForbiddenIp(String),
+    #[error("code used: {0}")]
+    CodeUsed(String),
     #[error("code blocked: {0}")]
     CodeBlocked(String),
     #[error("secret frozen: {0}")]
@@ -38,6 +52,10 @@ pub enum Error {
     MissingSecret(String),
     #[error("missing code: {0}")]
     MissingCode(String),
+    #[error("access token expired: {0}")]
+    AccessTokenExpired(String),
+    #[error("refresh token expired: {0}")]
+    RefreshTokenExpired(String),
     #[error("required post method: {0}")]
     RequiredPostMethod(String),
     #[error("daily request limit exceeded: {0}")]
@@ -50,6 +68,8 @@ pub enum Error {
     AccountFrozen(String),
     #[error("third party token: {0}")]
     ThirdPartyToken(String),
+    #[error("user access expired: {0}")]
+    UserAccessExpired(String),
     #[error("session key not existed or expired: {0}")]
     SessionKeyNotExistedOrExpired(String),
     #[error("invalid signature method: {0}")]
@@ -68,12 +88,52 @@ pub enum Error {
     AesInvalidLength(#[from] AesInvalidLength),
     #[error("base64 decode error: {0}")]
     Base64Decode(#[from] Base64DecodeError),
+    #[cfg(feature = "reqwest")]
     #[error("reqwest: {0}")]
-    Reqwest(#[from] ReqwestError),
+    Reqwest(ReqwestError),
     #[error("json error: {0}")]
     SerdeJson(#[from] SerdeJsonError),
+    #[cfg(feature = "pay")]
+    #[error("pkcs8 error: {0}")]
+    Pkcs8(#[from] Pkcs8Error),
+    #[cfg(feature = "pay")]
+    #[error("spki error: {0}")]
+    Spki(#[from] SpkiError),
+    #[cfg(feature = "pay")]
+    #[error("der error: {0}")]
+    Der(#[from] DerError),
+    #[cfg(feature = "pay")]
+    #[error("aes-gcm error: {0}")]
+    AesGcm(AesGcmError),
+    #[cfg(feature = "pay")]
+    #[error("wechat pay verification failed: {0}")]
+    PayVerification(String),
+    #[cfg(feature = "pay")]
+    #[error("wechat pay: {0}")]
+    Pay(PayError),
+    #[error("url encode error: {0}")]
+    UrlEncoded(#[from] UrlEncodedError),
+    #[error("user encrypt key not found: version={0}")]
+    EncryptKeyNotFound(u32),
+    #[error("invalid identifier: {0}")]
+    InvalidIdentifier(String),
+    #[error("invalid date range: {0}")]
+    InvalidDateRange(String),
     #[error("internal error: {0}")]
     InternalServer(String),
+    /// 请求的 url 或请求头无效，或自定义 [`HttpTransport`](crate::transport::HttpTransport) 发送请求失败
+    #[error("transport error: {0}")]
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    #[error("unknown error: code={0}, message={1}")]
+    Unknown(i64, String),
+}
+
+// 请求的 url 中可能包含 access_token 或 secret，不保留在错误信息中
+#[cfg(feature = "reqwest")]
+impl From<ReqwestError> for Error {
+    fn from(error: ReqwestError) -> Self {
+        Error::Reqwest(error.without_url())
+    }
 }
 
 impl From<UnpadError> for Error {
@@ -82,6 +142,73 @@ impl From<UnpadError> for Error {
     }
 }
 
+#[cfg(feature = "pay")]
+impl From<AesGcmError> for Error {
+    fn from(error: AesGcmError) -> Self {
+        Error::AesGcm(error)
+    }
+}
+
+/// 微信支付 APIv3 返回的错误，与微信小程序的 [`ErrorCode`] 相互独立
+/// https://pay.weixin.qq.com/docs/merchant/development/interface-rules/error-code.html
+#[cfg(feature = "pay")]
+#[derive(Debug, Deserialize, Clone)]
+pub struct PayError {
+    #[serde(skip)]
+    status: u16,
+    code: String,
+    message: String,
+    #[serde(default)]
+    detail: Option<serde_json::Value>,
+}
+
+#[cfg(feature = "pay")]
+impl PayError {
+    /// 本地校验失败时构造的错误，没有 HTTP 状态码
+    pub(crate) fn new(code: &str, message: String) -> Self {
+        Self {
+            status: 0,
+            code: code.to_string(),
+            message,
+            detail: None,
+        }
+    }
+
+    pub(crate) fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// HTTP 状态码，本地校验失败时为 0
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// 详细错误码，如 `PARAM_ERROR`、`ORDER_NOT_EXIST`
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn detail(&self) -> Option<&serde_json::Value> {
+        self.detail.as_ref()
+    }
+}
+
+#[cfg(feature = "pay")]
+impl std::fmt::Display for PayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "status={}, code={}, message={}",
+            self.status, self.code, self.message
+        )
+    }
+}
+
 /// 微信小程序返回的错误码
 #[derive(Debug, Deserialize_repr, Display)]
 #[repr(i32)]
@@ -98,10 +225,14 @@ pub enum ErrorCode {
     InvalidAppId = 40013,
     #[strum(serialize = "code 无效")]
     InvalidCode = 40029,
+    #[strum(serialize = "不合法的 refresh_token")]
+    InvalidRefreshToken = 40030,
     #[strum(serialize = "参数错误")]
     InvalidParameter = 40097,
     #[strum(serialize = "无效的appsecret，请检查appsecret的正确性")]
     InvalidSecret = 40125,
+    #[strum(serialize = "code 已被使用")]
+    CodeUsed = 40163,
     #[strum(serialize = "将ip添加到ip白名单列表即可")]
     ForbiddenIp = 40164,
     #[strum(serialize = "高风险等级用户，小程序登录拦截 。风险等级详见用户安全解方案")]
@@ -115,6 +246,10 @@ pub enum ErrorCode {
     #[strum(serialize = "缺少 secret 参数")]
     MissingSecret = 41004,
     MissingCode = 41008,
+    #[strum(serialize = "access_token 超时，请检查 access_token 的有效期")]
+    AccessTokenExpired = 42001,
+    #[strum(serialize = "refresh_token 超时")]
+    RefreshTokenExpired = 42002,
     #[strum(serialize = "需要 POST 请求")]
     RequiredPostMethod = 43002,
     #[strum(serialize = "调用超过天级别频率限制。可调用clear_quota接口恢复调用额度。")]
@@ -125,6 +260,8 @@ pub enum ErrorCode {
     ForbiddenToken = 50004,
     #[strum(serialize = "账号已冻结")]
     AccountFrozen = 50007,
+    #[strum(serialize = "用户访问记录超时，用户未在近两小时访问小程序")]
+    UserAccessExpired = 61010,
     #[strum(serialize = "第三方平台 API 需要使用第三方平台专用 token")]
     ThirdPartyToken = 61024,
     #[strum(serialize = "session_key is not existed or expired")]
@@ -155,8 +292,10 @@ impl From<(ErrorCode, String)> for Error {
             InvalidGrantType => Error::InvalidGrantType(message),
             InvalidAppId => Error::InvalidAppId(message),
             InvalidCode => Error::InvalidCode(message),
+            InvalidRefreshToken => Error::InvalidRefreshToken(message),
             InvalidParameter => Error::InvalidParameter(message),
             InvalidSecret => Error::InvalidSecret(message),
+            CodeUsed => Error::CodeUsed(message),
             ForbiddenIp => Error::ForbiddenIp(message),
             CodeBlocked => Error::CodeBlocked(message),
             SecretFrozen => Error::SecretFrozen(message),
@@ -164,11 +303,14 @@ impl From<(ErrorCode, String)> for Error {
             MissingAppId => Error::MissingAppId(message),
             MissingSecret => Error::MissingSecret(message),
             MissingCode => Error::MissingCode(message),
+            AccessTokenExpired => Error::AccessTokenExpired(message),
+            RefreshTokenExpired => Error::RefreshTokenExpired(message),
             RequiredPostMethod => Error::RequiredPostMethod(message),
             DailyRequestLimitExceeded => Error::DailyRequestLimitExceeded(message),
             RateLimitExceeded => Error::RateLimitExceeded(message),
             ForbiddenToken => Error::ForbiddenToken(message),
             AccountFrozen => Error::AccountFrozen(message),
+            UserAccessExpired => Error::UserAccessExpired(message),
             ThirdPartyToken => Error::ThirdPartyToken(message),
             SessionKeyNotExistedOrExpired => Error::SessionKeyNotExistedOrExpired(message),
             InvalidSignatureMethod => Error::InvalidSignatureMethod(message),
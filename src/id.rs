@@ -0,0 +1,213 @@
+//! 带校验的标识符类型，避免混用 openid、session_key 等同为字符串的参数
+
+use std::{fmt, ops::Deref, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{error::Error::InvalidIdentifier, secret::Secret, Result};
+
+macro_rules! identifier {
+    ($(#[$meta:meta])* $name:ident($inner:ty), $get:ident, $field:literal, $validate:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, PartialEq, Eq, Hash, Deserialize)]
+        #[serde(try_from = "String")]
+        pub struct $name($inner);
+
+        impl $name {
+            /// 校验并创建，校验失败时返回 [`Error::InvalidIdentifier`](crate::error::Error::InvalidIdentifier)
+            pub fn new(value: impl Into<String>) -> Result<Self> {
+                let value = <$inner>::from(value.into());
+
+                let validate: fn(&str) -> bool = $validate;
+
+                if validate(value.$get()) {
+                    Ok(Self(value))
+                } else {
+                    Err(InvalidIdentifier($field.into()))
+                }
+            }
+
+            pub fn as_str(&self) -> &str {
+                self.0.$get()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = crate::error::Error;
+
+            fn from_str(value: &str) -> Result<Self> {
+                Self::new(value)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = crate::error::Error;
+
+            fn try_from(value: String) -> Result<Self> {
+                Self::new(value)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = crate::error::Error;
+
+            fn try_from(value: &str) -> Result<Self> {
+                Self::new(value)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+    };
+}
+
+macro_rules! public_identifier {
+    ($(#[$meta:meta])* $name:ident, $field:literal, $validate:expr) => {
+        identifier!($(#[$meta])* $name(String), as_str, $field, $validate);
+
+        impl $name {
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.0).finish()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+    };
+}
+
+/// 敏感的标识符以 [`Secret`] 存储，`Debug` 不打印具体的值，释放时清零内存
+macro_rules! secret_identifier {
+    ($(#[$meta:meta])* $name:ident, $field:literal, $validate:expr) => {
+        identifier!($(#[$meta])* $name(Secret), expose, $field, $validate);
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.0).finish()
+            }
+        }
+    };
+}
+
+/// 由字母、数字、`-` 和 `_` 组成，长度不超过 `max`
+fn is_token(value: &str, max: usize) -> bool {
+    !value.is_empty()
+        && value.len() <= max
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+}
+
+public_identifier!(
+    /// 用户在小程序或公众号下的唯一标识 openid
+    OpenId,
+    "open_id",
+    |value| is_token(value, 64)
+);
+
+impl OpenId {
+    /// 部分接口以空字符串表示没有 openid
+    pub(crate) fn deserialize_optional<'de, D>(
+        deserializer: D,
+    ) -> std::result::Result<Option<Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        Self::new(value).map(Some).map_err(D::Error::custom)
+    }
+
+    /// 部分接口以空字符串表示不按 openid 过滤
+    pub(crate) fn serialize_optional<S>(
+        open_id: &Option<Self>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(open_id.as_ref().map(Self::as_str).unwrap_or_default())
+    }
+}
+
+public_identifier!(
+    /// 用户在开放平台下的唯一标识 unionid
+    UnionId,
+    "union_id",
+    |value| is_token(value, 64)
+);
+
+public_identifier!(
+    /// 小程序、公众号或网站应用的 appid，以 `wx` 开头，共 18 位
+    /// ```rust
+    /// use open_wechat::id::AppId;
+    ///
+    /// assert!(AppId::new("wx1234567890abcdef").is_ok());
+    /// assert!(AppId::new("your app id").is_err());
+    /// ```
+    AppId,
+    "app_id",
+    |value| value.len() == 18 && value.starts_with("wx") && is_token(value, 18)
+);
+
+secret_identifier!(
+    /// 会话密钥 session_key，为 base64 编码的 16 字节密钥
+    /// ```rust
+    /// use open_wechat::id::SessionKey;
+    ///
+    /// let session_key = SessionKey::new("tiihtNczf5v6AKRyjwEUhQ==").unwrap();
+    ///
+    /// assert_eq!(format!("{:?}", session_key), "SessionKey(********)");
+    /// assert!(SessionKey::new("not a session key").is_err());
+    /// ```
+    SessionKey,
+    "session_key",
+    |value| STANDARD.decode(value).is_ok_and(|key| key.len() == 16)
+);
+
+secret_identifier!(
+    /// `wx.login` 返回的临时登录凭证 code
+    LoginCode,
+    "login_code",
+    |value| is_token(value, 128)
+);
@@ -0,0 +1,291 @@
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    client::Client, credential::TokenProvider, secret::Secret, transport::Multipart, Result,
+};
+
+/// 待处理的图片，图片链接或图片文件二选一，图片文件以 multipart/form-data 方式上传
+#[derive(Debug, Clone)]
+pub enum Image {
+    /// 图片的 url
+    Url(String),
+    /// 图片文件的内容
+    Bytes(Vec<u8>),
+}
+
+#[derive(Serialize)]
+struct ImageQuery<'a> {
+    img_url: &'a str,
+}
+
+impl Image {
+    /// 上传图片或图片链接
+    pub(crate) async fn post<T>(
+        &self,
+        client: &Client,
+        url: &str,
+        access_token: &Secret,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Self::Url(img_url) => {
+                let query = ImageQuery { img_url };
+
+                client.post_query(url, access_token, &query).await
+            }
+            Self::Bytes(bytes) => {
+                let form = Multipart::new().file("img", "img", bytes);
+
+                client.post_multipart(url, access_token, &(), form).await
+            }
+        }
+    }
+}
+
+impl From<Vec<u8>> for Image {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<&str> for Image {
+    fn from(url: &str) -> Self {
+        Self::Url(url.into())
+    }
+}
+
+impl From<String> for Image {
+    fn from(url: String) -> Self {
+        Self::Url(url)
+    }
+}
+
+/// 图片尺寸
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct ImageSize {
+    w: u32,
+    h: u32,
+}
+
+impl ImageSize {
+    pub fn width(&self) -> u32 {
+        self.w
+    }
+
+    pub fn height(&self) -> u32 {
+        self.h
+    }
+}
+
+/// 坐标
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl Point {
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+
+    pub fn y(&self) -> i64 {
+        self.y
+    }
+}
+
+/// 四个顶点的坐标
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct Position {
+    left_top: Point,
+    right_top: Point,
+    right_bottom: Point,
+    left_bottom: Point,
+}
+
+impl Position {
+    pub fn left_top(&self) -> Point {
+        self.left_top
+    }
+
+    pub fn right_top(&self) -> Point {
+        self.right_top
+    }
+
+    pub fn right_bottom(&self) -> Point {
+        self.right_bottom
+    }
+
+    pub fn left_bottom(&self) -> Point {
+        self.left_bottom
+    }
+}
+
+/// 图片智能裁剪的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AiCrop {
+    #[serde(default)]
+    results: Vec<CropResult>,
+    img_size: ImageSize,
+}
+
+impl AiCrop {
+    pub fn results(&self) -> &[CropResult] {
+        &self.results
+    }
+
+    pub fn img_size(&self) -> ImageSize {
+        self.img_size
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct CropResult {
+    crop_left: u32,
+    crop_top: u32,
+    crop_right: u32,
+    crop_bottom: u32,
+}
+
+impl CropResult {
+    pub fn crop_left(&self) -> u32 {
+        self.crop_left
+    }
+
+    pub fn crop_top(&self) -> u32 {
+        self.crop_top
+    }
+
+    pub fn crop_right(&self) -> u32 {
+        self.crop_right
+    }
+
+    pub fn crop_bottom(&self) -> u32 {
+        self.crop_bottom
+    }
+}
+
+/// 条码、二维码识别的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QrCode {
+    #[serde(default)]
+    code_results: Vec<CodeResult>,
+    img_size: ImageSize,
+}
+
+impl QrCode {
+    pub fn code_results(&self) -> &[CodeResult] {
+        &self.code_results
+    }
+
+    pub fn img_size(&self) -> ImageSize {
+        self.img_size
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CodeResult {
+    type_name: String,
+    data: String,
+    #[serde(default)]
+    pos: Option<Position>,
+}
+
+impl CodeResult {
+    /// 码的类型，如 `QR_CODE`、`EAN_13`
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// 码的内容
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    pub fn pos(&self) -> Option<Position> {
+        self.pos
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SuperResolutionBuilder {
+    media_id: String,
+}
+
+/// 图像处理接口
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{
+///     credential::GenericAccessToken,
+///     img::{Image, ProcessImage},
+///     Result,
+/// };
+/// use serde_json::json;
+///
+/// pub(crate) async fn scan(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let image = Image::from("https://example.com/qrcode.jpg");
+///
+///     let qrcode = access_token.scan_qr_code(&image).await?;
+///
+///     Ok(Json(json!({
+///         "qrcode": qrcode,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait ProcessImage {
+    const AI_CROP: &'static str = "https://api.weixin.qq.com/cv/img/aicrop";
+    const SCAN_QR_CODE: &'static str = "https://api.weixin.qq.com/cv/img/qrcode";
+    const SUPER_RESOLUTION: &'static str = "https://api.weixin.qq.com/cv/img/superresolution";
+
+    /// 图片智能裁剪
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/img/aiCrop.html
+    async fn ai_crop(&self, image: &Image) -> Result<AiCrop>;
+
+    /// 条码、二维码识别
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/img/scanQRCode.html
+    async fn scan_qr_code(&self, image: &Image) -> Result<QrCode>;
+
+    /// 图片高清化，返回高清化后图片的 media_id
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/img/superResolution.html
+    async fn super_resolution(&self, image: &Image) -> Result<String>;
+}
+
+#[async_trait]
+impl<T> ProcessImage for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, image))]
+    async fn ai_crop(&self, image: &Image) -> Result<AiCrop> {
+        image
+            .post(self.client(), Self::AI_CROP, &self.token().await?)
+            .await
+    }
+
+    #[instrument(skip(self, image))]
+    async fn scan_qr_code(&self, image: &Image) -> Result<QrCode> {
+        image
+            .post(self.client(), Self::SCAN_QR_CODE, &self.token().await?)
+            .await
+    }
+
+    #[instrument(skip(self, image))]
+    async fn super_resolution(&self, image: &Image) -> Result<String> {
+        let builder = image
+            .post::<SuperResolutionBuilder>(
+                self.client(),
+                Self::SUPER_RESOLUTION,
+                &self.token().await?,
+            )
+            .await?;
+
+        Ok(builder.media_id)
+    }
+}
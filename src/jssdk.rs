@@ -0,0 +1,383 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use hex::encode;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Deserializer, Serialize};
+use sha1::{Digest, Sha1};
+use tokio::sync::RwLock;
+use tracing::{event, instrument, Level};
+
+use crate::{
+    credential::{GenericAccessToken, TokenProvider},
+    id::OpenId,
+    refresh::RefreshOnce,
+    secret::Secret,
+    Result,
+};
+
+/// 公众号 JS-SDK 使用的临时票据类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TicketType {
+    /// 调用 `wx.config` 使用的 jsapi_ticket
+    #[serde(rename = "jsapi")]
+    JsApi,
+    /// 调用微信卡券接口使用的 api_ticket
+    #[serde(rename = "wx_card")]
+    WxCard,
+}
+
+#[derive(Serialize)]
+struct TicketQuery {
+    #[serde(rename = "type")]
+    ticket_type: TicketType,
+}
+
+#[derive(Deserialize)]
+struct TicketBuilder {
+    ticket: Secret,
+    #[serde(
+        deserialize_with = "TicketBuilder::deserialize_expired_at",
+        rename = "expires_in"
+    )]
+    expired_at: DateTime<Utc>,
+}
+
+impl TicketBuilder {
+    fn deserialize_expired_at<'de, D>(
+        deserializer: D,
+    ) -> std::result::Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let seconds = Duration::seconds(i64::deserialize(deserializer)?);
+
+        Ok(Utc::now() + seconds)
+    }
+}
+
+struct Ticket {
+    ticket: Secret,
+    expired_at: DateTime<Utc>,
+}
+
+impl std::fmt::Debug for Ticket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ticket")
+            .field("ticket", &"********")
+            .field("expired_at", &self.expired_at)
+            .finish()
+    }
+}
+
+/// 缓存的 jsapi_ticket 或卡券 api_ticket，过期后使用 access_token 自动刷新
+#[derive(Debug, Clone)]
+pub struct GenericTicket<T = GenericAccessToken> {
+    inner: Arc<RwLock<Ticket>>,
+    refresh: Arc<RefreshOnce>,
+    ticket_type: TicketType,
+    access_token: T,
+}
+
+impl<T> GenericTicket<T>
+where
+    T: TokenProvider,
+{
+    const TICKET: &'static str = "https://api.weixin.qq.com/cgi-bin/ticket/getticket";
+
+    /// 获取临时票据，`access_token` 需使用公众号的 appid 和 secret
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/JS-SDK.html
+    /// ```ignore
+    /// use open_wechat::{
+    ///     client::Client,
+    ///     credential::{GenericAccessToken, GetAccessToken},
+    ///     jssdk::{GenericTicket, TicketType},
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your app id", "your app secret");
+    ///
+    ///     let access_token = GenericAccessToken::new(client).await?;
+    ///
+    ///     let ticket = GenericTicket::new(access_token, TicketType::JsApi).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn new(access_token: T, ticket_type: TicketType) -> Result<Self> {
+        let builder = Self::fetch(&access_token, ticket_type).await?;
+
+        Ok(Self {
+            inner: Arc::new(RwLock::new(Ticket {
+                ticket: builder.ticket,
+                expired_at: builder.expired_at,
+            })),
+            refresh: Arc::new(RefreshOnce::default()),
+            ticket_type,
+            access_token,
+        })
+    }
+
+    pub fn ticket_type(&self) -> TicketType {
+        self.ticket_type
+    }
+
+    #[instrument(skip(access_token))]
+    async fn fetch(access_token: &T, ticket_type: TicketType) -> Result<TicketBuilder> {
+        access_token
+            .client()
+            .get(
+                Self::TICKET,
+                &access_token.token().await?,
+                &TicketQuery { ticket_type },
+            )
+            .await
+    }
+
+    /// 有效的临时票据，过期时只有一个调用者刷新，其余调用者等待刷新完成
+    pub async fn ticket(&self) -> Result<Secret> {
+        self.refresh
+            .get(
+                || async {
+                    event!(Level::DEBUG, "read ticket guard");
+
+                    let guard = self.inner.read().await;
+
+                    if guard.expired_at <= Utc::now() {
+                        event!(Level::DEBUG, "expired at: {}", guard.expired_at);
+
+                        return None;
+                    }
+
+                    Some(guard.ticket.clone())
+                },
+                || async {
+                    let builder = Self::fetch(&self.access_token, self.ticket_type).await?;
+
+                    event!(Level::DEBUG, "write ticket guard");
+
+                    let mut guard = self.inner.write().await;
+
+                    guard.ticket = builder.ticket;
+                    guard.expired_at = builder.expired_at;
+
+                    event!(Level::DEBUG, "fresh ticket: {:#?}", guard);
+
+                    Ok(guard.ticket.clone())
+                },
+            )
+            .await
+    }
+}
+
+/// 生成 16 位随机字符串
+fn nonce() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect()
+}
+
+/// 计算 `wx.config` 的签名，对 `jsapi_ticket`、`noncestr`、`timestamp` 和 `url` 按字典序拼接后进行 sha1 签名，
+/// `url` 不包含 `#` 及其后面的部分
+/// ```rust
+/// use open_wechat::jssdk::jsapi_signature;
+///
+/// let signature = jsapi_signature(
+///     "sM4AOVdWfPE4DxkXGEs8VMCPGGVi4C3VM0P37wVUCFvkVAy_90u5h9nbSlYy3-Sl-HhTdfl2fzFy1AOcHKP7qg",
+///     "Wm3WZYTPz0wzccnW",
+///     1414587457,
+///     "http://mp.weixin.qq.com?params=value",
+/// );
+///
+/// assert_eq!(signature, "0f9de62fce790f9a083d5c99e95740ceb90c27ed");
+/// ```
+pub fn jsapi_signature(jsapi_ticket: &str, nonce_str: &str, timestamp: i64, url: &str) -> String {
+    let url = url.split('#').next().unwrap_or_default();
+
+    let content =
+        format!("jsapi_ticket={jsapi_ticket}&noncestr={nonce_str}&timestamp={timestamp}&url={url}");
+
+    encode(Sha1::digest(content.as_bytes()))
+}
+
+/// 计算卡券签名，将所有参与签名的值按字典序排序后拼接，再进行 sha1 签名
+pub fn card_signature(values: &[&str]) -> String {
+    let mut values = values.to_vec();
+
+    values.sort_unstable();
+
+    encode(Sha1::digest(values.concat().as_bytes()))
+}
+
+/// `wx.config` 需要的参数
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JsSdkConfig {
+    app_id: String,
+    timestamp: i64,
+    nonce_str: String,
+    signature: String,
+}
+
+impl JsSdkConfig {
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    pub fn nonce_str(&self) -> &str {
+        &self.nonce_str
+    }
+
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+}
+
+/// 使用 jsapi_ticket 生成 `wx.config` 的签名
+/// ```rust
+/// use axum::{
+///     extract::{Query, State},
+///     response::IntoResponse,
+///     Json,
+/// };
+/// use open_wechat::{jssdk::JsSdkSigner, Result};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// pub(crate) struct Page {
+///     url: String,
+/// }
+///
+/// pub(crate) async fn config(
+///     State(signer): State<JsSdkSigner>,
+///     Query(page): Query<Page>,
+/// ) -> Result<impl IntoResponse> {
+///     let config = signer.sign(&page.url).await?;
+///
+///     Ok(Json(config))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsSdkSigner<T = GenericAccessToken> {
+    ticket: GenericTicket<T>,
+}
+
+impl<T> JsSdkSigner<T>
+where
+    T: TokenProvider,
+{
+    pub async fn new(access_token: T) -> Result<Self> {
+        let ticket = GenericTicket::new(access_token, TicketType::JsApi).await?;
+
+        Ok(Self { ticket })
+    }
+
+    /// 为当前网页的 `url` 生成 `wx.config` 的参数
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/JS-SDK.html#62
+    #[instrument(skip(self))]
+    pub async fn sign(&self, url: &str) -> Result<JsSdkConfig> {
+        let nonce_str = nonce();
+        let timestamp = Utc::now().timestamp();
+
+        let signature = jsapi_signature(
+            self.ticket.ticket().await?.expose(),
+            &nonce_str,
+            timestamp,
+            url,
+        );
+
+        Ok(JsSdkConfig {
+            app_id: self.ticket.access_token.client().app_id().into(),
+            timestamp,
+            nonce_str,
+            signature,
+        })
+    }
+}
+
+/// `wx.addCard` 的 `cardExt` 参数
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CardExt {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(rename = "openid", skip_serializing_if = "Option::is_none")]
+    open_id: Option<OpenId>,
+    timestamp: String,
+    nonce_str: String,
+    signature: String,
+}
+
+impl CardExt {
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    pub fn open_id(&self) -> Option<&OpenId> {
+        self.open_id.as_ref()
+    }
+
+    pub fn timestamp(&self) -> &str {
+        &self.timestamp
+    }
+
+    pub fn nonce_str(&self) -> &str {
+        &self.nonce_str
+    }
+
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+}
+
+/// 使用卡券 api_ticket 生成卡券接口的签名
+#[derive(Debug, Clone)]
+pub struct CardSigner<T = GenericAccessToken> {
+    ticket: GenericTicket<T>,
+}
+
+impl<T> CardSigner<T>
+where
+    T: TokenProvider,
+{
+    pub async fn new(access_token: T) -> Result<Self> {
+        let ticket = GenericTicket::new(access_token, TicketType::WxCard).await?;
+
+        Ok(Self { ticket })
+    }
+
+    /// 生成 `wx.addCard` 的 `cardExt`，`code` 和 `open_id` 仅在卡券为自定义 code 或指定用户领取时传入
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/JS-SDK.html#65
+    #[instrument(skip(self, open_id))]
+    pub async fn sign_card_ext(
+        &self,
+        card_id: &str,
+        code: Option<&str>,
+        open_id: Option<&OpenId>,
+    ) -> Result<CardExt> {
+        let nonce_str = nonce();
+        let timestamp = Utc::now().timestamp().to_string();
+        let ticket = self.ticket.ticket().await?;
+
+        let mut values = vec![ticket.expose(), &timestamp, card_id, &nonce_str];
+
+        values.extend(code);
+        values.extend(open_id.map(OpenId::as_str));
+
+        let signature = card_signature(&values);
+
+        Ok(CardExt {
+            code: code.map(Into::into),
+            open_id: open_id.cloned(),
+            timestamp,
+            nonce_str,
+            signature,
+        })
+    }
+}
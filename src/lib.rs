@@ -52,13 +52,12 @@
 //!
 //! ```rust
 //! use axum::{extract::State, response::IntoResponse, Json};
-//! use open_wechat::{client::Client, Result};
+//! use open_wechat::{client::Client, id::LoginCode, Result};
 //! use serde::Deserialize;
 //!
-//! #[derive(Deserialize, Default)]
-//! #[serde(default)]
+//! #[derive(Deserialize)]
 //! pub(crate) struct Logger {
-//!     code: String,
+//!     code: LoginCode,
 //! }
 //!
 //! pub(crate) async fn login(
@@ -75,12 +74,12 @@
 //!
 //! ```rust
 //! use axum::{extract::State, response::IntoResponse, Json};
-//! use open_wechat::{client::Client, Result};
+//! use open_wechat::{client::Client, id::LoginCode, Result};
 //! use serde::Deserialize;
 //!
-//! #[derive(Deserialize, Default)]
+//! #[derive(Deserialize)]
 //! pub(crate) struct EncryptedPayload {
-//!     code: String,
+//!     code: LoginCode,
 //!     encrypted_data: String,
 //!     iv: String,
 //! }
@@ -101,15 +100,18 @@
 //!
 //! ```rust
 //! use axum::{extract::State, response::IntoResponse, Json};
-//! use open_wechat::{client::Client, credential::{GenericAccessToken, CheckSessionKey}, Result};
+//! use open_wechat::{
+//!     credential::{CheckSessionKey, GenericAccessToken},
+//!     id::{OpenId, SessionKey},
+//!     Result,
+//! };
 //! use serde::Deserialize;
 //! use serde_json::json;
 //!
-//! #[derive(Debug, Deserialize, Default)]
-//! #[serde(default)]
+//! #[derive(Debug, Deserialize)]
 //! pub(crate) struct SessionKeyChecker {
-//!     session_key: String,
-//!     open_id: String,
+//!     session_key: SessionKey,
+//!     open_id: OpenId,
 //! }
 //!
 //! pub(crate) async fn check_session_key(
@@ -132,15 +134,18 @@
 //!
 //! ```rust
 //! use axum::{extract::State, response::IntoResponse, Json};
-//! use open_wechat::{client::Client, credential::{GenericAccessToken, ResetSessionKey}, Result};
+//! use open_wechat::{
+//!     credential::{GenericAccessToken, ResetSessionKey},
+//!     id::{OpenId, SessionKey},
+//!     Result,
+//! };
 //! use serde::Deserialize;
 //! use serde_json::json;
 //!
-//! #[derive(Debug, Deserialize, Default)]
-//! #[serde(default)]
+//! #[derive(Debug, Deserialize)]
 //! pub(crate) struct SessionKeyResetter {
-//!     session_key: String,
-//!     open_id: String,
+//!     session_key: SessionKey,
+//!     open_id: OpenId,
 //! }
 //!
 //! pub(crate) async fn reset_session_key(
@@ -157,6 +162,31 @@
 //!     })))
 //! }
 //! ```
+//!
+//! ### Blocking
+//!
+//! 启用 `blocking` feature 后，可以在不使用 tokio 运行时的批处理任务和命令行工具中同步调用
+//!
+//! ```ignore
+//! use open_wechat::{
+//!     blocking::{CheckSessionKey, Client, GenericAccessToken, GetAccessToken},
+//!     id::LoginCode,
+//! };
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = Client::new("your app id", "your app secret");
+//!
+//!     let code: LoginCode = "your login code".parse()?;
+//!
+//!     let credential = client.login(&code)?;
+//!
+//!     let access_token = GenericAccessToken::new(client)?;
+//!
+//!     access_token.check_session_key(credential.session_key(), credential.open_id())?;
+//!
+//!     Ok(())
+//! }
+//! ```
 
 #![warn(
     clippy::all,
@@ -167,10 +197,34 @@
 #![allow(rustdoc::bare_urls)]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
+pub mod cloud;
 pub mod credential;
+pub mod datacube;
+pub mod device;
 pub mod error;
+pub mod id;
+pub mod img;
+pub mod jssdk;
+pub mod limit;
+pub mod live;
+pub mod logistics;
+pub mod oauth;
+pub mod ocr;
+pub mod operation;
+#[cfg(feature = "pay")]
+pub mod pay;
+pub mod redpacket;
+mod refresh;
 mod response;
+pub mod risk;
+pub mod secret;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod shipping;
+pub mod transport;
 pub mod user;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -0,0 +1,354 @@
+//! 客户端限流，按接口配置令牌桶，避免触发微信的调用频率限制（45009、45011）
+//!
+//! 接口按请求 url 的路径区分，如 `/cgi-bin/token`，[`RateLimiter::default`] 包含获取 access_token 接口公布的限额
+//! ```rust
+//! use async_trait::async_trait;
+//! use open_wechat::{
+//!     client::Client,
+//!     error::Error,
+//!     id::LoginCode,
+//!     limit::{Quota, RateLimitedTransport, RateLimiter, Strategy},
+//!     transport::{HttpRequest, HttpResponse, HttpTransport},
+//!     Result,
+//! };
+//! use http::{HeaderMap, StatusCode};
+//!
+//! #[derive(Debug)]
+//! struct MemoryTransport;
+//!
+//! #[async_trait]
+//! impl HttpTransport for MemoryTransport {
+//!     async fn send(&self, _: HttpRequest) -> Result<HttpResponse> {
+//!         Ok(HttpResponse {
+//!             status: StatusCode::OK,
+//!             headers: HeaderMap::new(),
+//!             body: br#"{"openid":"oUpF8uMuAJO_M2pxb1Q9zNjWeS6o","session_key":"tiihtNczf5v6AKRyjwEUhQ=="}"#
+//!                 .to_vec(),
+//!         })
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     let limiter = RateLimiter::new(Strategy::FailFast).limit("/sns/jscode2session", Quota::per_day(1));
+//!
+//!     let client = Client::with_transport(
+//!         "your app id",
+//!         "your app secret",
+//!         RateLimitedTransport::new(MemoryTransport, limiter.clone()),
+//!     );
+//!
+//!     let code = LoginCode::new("your_login_code")?;
+//!
+//!     client.login(&code).await?;
+//!
+//!     assert_eq!(limiter.remaining("/sns/jscode2session"), Some(0));
+//!     assert!(matches!(client.login(&code).await, Err(Error::RateLimitExceeded(_))));
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::{event, Level};
+
+use crate::{
+    error::Error::RateLimitExceeded,
+    transport::{HttpRequest, HttpResponse, HttpTransport},
+    Result,
+};
+
+/// 额度用尽时的处理方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// 等待令牌恢复后再发送请求
+    #[default]
+    Queue,
+    /// 立即返回 [`Error::RateLimitExceeded`](crate::error::Error::RateLimitExceeded)
+    FailFast,
+}
+
+/// 每个周期内允许的调用次数，令牌在周期内匀速恢复
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    capacity: u32,
+    period: Duration,
+}
+
+impl Quota {
+    /// `capacity` 至少为 1，`period` 至少为 1 纳秒
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use open_wechat::limit::Quota;
+    ///
+    /// let quota = Quota::new(0, Duration::ZERO);
+    ///
+    /// assert_eq!(quota.capacity(), 1);
+    /// assert_eq!(quota.period(), Duration::from_nanos(1));
+    /// ```
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            period: period.max(Duration::from_nanos(1)),
+        }
+    }
+
+    pub fn per_minute(capacity: u32) -> Self {
+        Self::new(capacity, Duration::from_secs(60))
+    }
+
+    pub fn per_day(capacity: u32) -> Self {
+        Self::new(capacity, Duration::from_secs(24 * 60 * 60))
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    quota: Quota,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            tokens: quota.capacity as f64,
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// 每秒恢复的令牌数
+    fn rate(&self) -> f64 {
+        self.quota.capacity as f64 / self.quota.period.as_secs_f64()
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.rate()).min(self.quota.capacity as f64);
+        self.updated_at = now;
+    }
+
+    /// 距离下一个令牌可用的时间
+    fn wait(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            // 周期很长时等待时间可能超出 `Duration` 的范围
+            Duration::try_from_secs_f64((1.0 - self.tokens) / self.rate()).unwrap_or(Duration::MAX)
+        }
+    }
+}
+
+/// 按接口配置的令牌桶限流器，克隆后共享额度
+/// ```rust
+/// use open_wechat::limit::{Quota, RateLimiter, Strategy};
+///
+/// let limiter = RateLimiter::default().limit("/wxa/business/getuserphonenumber", Quota::per_minute(100));
+///
+/// assert_eq!(limiter.remaining("/cgi-bin/token"), Some(2000));
+/// assert_eq!(limiter.remaining("/wxa/business/getuserphonenumber"), Some(100));
+/// assert_eq!(limiter.remaining("/wxa/checksession"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    strategy: Strategy,
+    buckets: Arc<Mutex<HashMap<String, Vec<Bucket>>>>,
+}
+
+impl RateLimiter {
+    /// 不包含任何限额的限流器
+    pub fn new(strategy: Strategy) -> Self {
+        Self {
+            strategy,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 为接口添加限额，同一接口可以同时配置每分钟和每日的限额
+    pub fn limit(self, path: &str, quota: Quota) -> Self {
+        self.lock()
+            .entry(path.into())
+            .or_default()
+            .push(Bucket::new(quota));
+
+        self
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// 接口剩余的可调用次数，未配置限额的接口返回 `None`
+    pub fn remaining(&self, path: &str) -> Option<u32> {
+        let now = Instant::now();
+        let mut buckets = self.lock();
+
+        buckets.get_mut(path).map(|buckets| {
+            buckets
+                .iter_mut()
+                .map(|bucket| {
+                    bucket.refill(now);
+
+                    bucket.tokens as u32
+                })
+                .min()
+                .unwrap_or_default()
+        })
+    }
+
+    /// 获取一个令牌，额度用尽时按 [`Strategy`] 等待或返回错误
+    pub(crate) async fn acquire(&self, path: &str) -> Result<()> {
+        loop {
+            let wait = match self.try_acquire(path) {
+                Some(wait) => wait,
+                None => return Ok(()),
+            };
+
+            event!(Level::WARN, "rate limited: {}, wait: {:?}", path, wait);
+
+            match self.strategy {
+                Strategy::Queue => tokio::time::sleep(wait).await,
+                Strategy::FailFast => {
+                    return Err(RateLimitExceeded(format!(
+                        "client side quota of {path} exhausted, retry after {wait:?}"
+                    )))
+                }
+            }
+        }
+    }
+
+    /// 所有限额都有令牌时扣除并返回 `None`，否则返回需要等待的时间
+    fn try_acquire(&self, path: &str) -> Option<Duration> {
+        let now = Instant::now();
+        let mut buckets = self.lock();
+
+        let buckets = buckets.get_mut(path)?;
+
+        let wait = buckets
+            .iter_mut()
+            .map(|bucket| {
+                bucket.refill(now);
+
+                bucket.wait()
+            })
+            .max()
+            .unwrap_or_default();
+
+        if !wait.is_zero() {
+            return Some(wait);
+        }
+
+        for bucket in buckets.iter_mut() {
+            bucket.tokens -= 1.0;
+        }
+
+        None
+    }
+
+    /// 微信返回调用频率限制错误时，清空接口的令牌，避免继续消耗额度
+    fn exhaust(&self, path: &str) {
+        if let Some(buckets) = self.lock().get_mut(path) {
+            for bucket in buckets.iter_mut() {
+                bucket.tokens = 0.0;
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Vec<Bucket>>> {
+        self.buckets.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for RateLimiter {
+    /// 排队等待，包含公布的限额：
+    /// - `/cgi-bin/token`：每日 2000 次
+    /// - `/cgi-bin/stable_token`：每分钟 1 万次，每日 50 万次
+    fn default() -> Self {
+        Self::new(Strategy::Queue)
+            .limit("/cgi-bin/token", Quota::per_day(2000))
+            .limit("/cgi-bin/stable_token", Quota::per_minute(10_000))
+            .limit("/cgi-bin/stable_token", Quota::per_day(500_000))
+    }
+}
+
+/// 发送请求前从 [`RateLimiter`] 获取令牌的传输层
+/// ```rust
+/// use open_wechat::{
+///     client::Client,
+///     limit::{RateLimitedTransport, RateLimiter},
+///     transport::ReqwestTransport,
+/// };
+///
+/// let limiter = RateLimiter::default();
+///
+/// let client = Client::with_transport(
+///     "your app id",
+///     "your app secret",
+///     RateLimitedTransport::new(ReqwestTransport::default(), limiter.clone()),
+/// );
+/// ```
+#[derive(Debug)]
+pub struct RateLimitedTransport<T> {
+    transport: T,
+    limiter: RateLimiter,
+}
+
+impl<T> RateLimitedTransport<T> {
+    pub fn new(transport: T, limiter: RateLimiter) -> Self {
+        Self { transport, limiter }
+    }
+
+    pub fn limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorCode {
+    #[serde(default)]
+    errcode: i64,
+}
+
+#[async_trait]
+impl<T> HttpTransport for RateLimitedTransport<T>
+where
+    T: HttpTransport,
+{
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let path = request.url.path().to_owned();
+
+        self.limiter.acquire(&path).await?;
+
+        let response = self.transport.send(request).await?;
+
+        // 45009：接口调用超过每日限额，45011：调用太频繁
+        if let Ok(ErrorCode {
+            errcode: 45009 | 45011,
+        }) = serde_json::from_slice::<ErrorCode>(&response.body)
+        {
+            event!(Level::WARN, "quota of {} exhausted by wechat", path);
+
+            self.limiter.exhaust(&path);
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,786 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_repr::Serialize_repr;
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, id::OpenId, Result};
+
+/// 直播类型
+#[derive(Debug, Serialize_repr, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum LiveType {
+    /// 手机直播
+    #[default]
+    Phone = 0,
+    /// 推流
+    Push = 1,
+}
+
+/// 创建直播间的参数，`close_*` 字段为 1 时关闭对应功能
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRoom {
+    /// 直播间名字，最短 3 个汉字，最长 17 个汉字
+    pub name: String,
+    /// 背景图的 media_id，建议像素 1080*1920，大小不超过 2M
+    pub cover_img: String,
+    /// 直播计划开始时间，开播时间需要在当前时间的 10 分钟后，并且不能在 6 个月后
+    pub start_time: i64,
+    /// 直播计划结束时间，开播时间和结束时间间隔不得短于 30 分钟，不得超过 24 小时
+    pub end_time: i64,
+    /// 主播昵称
+    pub anchor_name: String,
+    /// 主播微信号，需要先实名认证
+    pub anchor_wechat: String,
+    /// 主播副号微信号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_anchor_wechat: Option<String>,
+    /// 创建者微信号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creater_wechat: Option<String>,
+    /// 分享图的 media_id，建议像素 800*640，大小不超过 1M
+    pub share_img: String,
+    /// 购物直播频道封面图的 media_id，建议像素 800*800，大小不超过 100KB
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feeds_img: Option<String>,
+    /// 是否开启官方收录，1 开启，0 关闭
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_feeds_public: Option<u8>,
+    #[serde(rename = "type")]
+    pub live_type: LiveType,
+    pub close_like: u8,
+    pub close_goods: u8,
+    pub close_comment: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_replay: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_share: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_kf: Option<u8>,
+}
+
+/// 创建直播间的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreatedRoom {
+    #[serde(rename(deserialize = "roomId"))]
+    room_id: i64,
+    #[serde(default)]
+    qrcode_url: Option<String>,
+}
+
+impl CreatedRoom {
+    pub fn room_id(&self) -> i64 {
+        self.room_id
+    }
+
+    /// 主播未实名认证时返回的实名认证二维码
+    pub fn qrcode_url(&self) -> Option<&str> {
+        self.qrcode_url.as_deref()
+    }
+}
+
+/// 直播间列表
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiveRoomList {
+    #[serde(default)]
+    room_info: Vec<LiveRoomInfo>,
+    #[serde(default)]
+    total: u64,
+}
+
+impl LiveRoomList {
+    pub fn room_info(&self) -> &[LiveRoomInfo] {
+        &self.room_info
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// 直播间信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiveRoomInfo {
+    name: String,
+    #[serde(rename(deserialize = "roomid"))]
+    room_id: i64,
+    #[serde(default)]
+    cover_img: String,
+    #[serde(default)]
+    share_img: String,
+    live_status: u16,
+    start_time: i64,
+    end_time: i64,
+    #[serde(default)]
+    anchor_name: String,
+    #[serde(default)]
+    goods: Vec<LiveGoods>,
+    #[serde(default)]
+    live_type: u8,
+    #[serde(default)]
+    close_like: u8,
+    #[serde(default)]
+    close_goods: u8,
+    #[serde(default)]
+    close_comment: u8,
+    #[serde(default)]
+    close_kf: u8,
+    #[serde(default)]
+    close_replay: u8,
+    #[serde(default)]
+    is_feeds_public: u8,
+    #[serde(
+        rename(deserialize = "creater_openid"),
+        default,
+        deserialize_with = "OpenId::deserialize_optional"
+    )]
+    creater_open_id: Option<OpenId>,
+    #[serde(default)]
+    feeds_img: String,
+}
+
+impl LiveRoomInfo {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn room_id(&self) -> i64 {
+        self.room_id
+    }
+
+    pub fn cover_img(&self) -> &str {
+        &self.cover_img
+    }
+
+    pub fn share_img(&self) -> &str {
+        &self.share_img
+    }
+
+    /// 直播间状态，101 直播中，102 未开始，103 已结束，104 禁播，105 暂停，106 异常，107 已过期
+    pub fn live_status(&self) -> u16 {
+        self.live_status
+    }
+
+    pub fn start_time(&self) -> i64 {
+        self.start_time
+    }
+
+    pub fn end_time(&self) -> i64 {
+        self.end_time
+    }
+
+    pub fn anchor_name(&self) -> &str {
+        &self.anchor_name
+    }
+
+    pub fn goods(&self) -> &[LiveGoods] {
+        &self.goods
+    }
+
+    /// 直播类型，0 手机直播，1 推流
+    pub fn live_type(&self) -> u8 {
+        self.live_type
+    }
+
+    pub fn close_like(&self) -> bool {
+        self.close_like == 1
+    }
+
+    pub fn close_goods(&self) -> bool {
+        self.close_goods == 1
+    }
+
+    pub fn close_comment(&self) -> bool {
+        self.close_comment == 1
+    }
+
+    pub fn close_kf(&self) -> bool {
+        self.close_kf == 1
+    }
+
+    pub fn close_replay(&self) -> bool {
+        self.close_replay == 1
+    }
+
+    pub fn is_feeds_public(&self) -> bool {
+        self.is_feeds_public == 1
+    }
+
+    /// 创建者 openid
+    pub fn creater_open_id(&self) -> Option<&OpenId> {
+        self.creater_open_id.as_ref()
+    }
+
+    pub fn feeds_img(&self) -> &str {
+        &self.feeds_img
+    }
+}
+
+/// 直播间商品
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiveGoods {
+    goods_id: i64,
+    name: String,
+    #[serde(default)]
+    cover_img: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    price: i64,
+    #[serde(default)]
+    price2: i64,
+    #[serde(default)]
+    price_type: u8,
+    #[serde(rename(deserialize = "third_party_appid"), default)]
+    third_party_app_id: String,
+}
+
+impl LiveGoods {
+    pub fn goods_id(&self) -> i64 {
+        self.goods_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cover_img(&self) -> &str {
+        &self.cover_img
+    }
+
+    /// 商品小程序路径
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// 商品价格，单位为分
+    pub fn price(&self) -> i64 {
+        self.price
+    }
+
+    /// 区间价格的右边界或折扣价，单位为分
+    pub fn price2(&self) -> i64 {
+        self.price2
+    }
+
+    /// 价格类型，1 一口价，2 价格区间，3 折扣价
+    pub fn price_type(&self) -> u8 {
+        self.price_type
+    }
+
+    /// 第三方商品的小程序 appid
+    pub fn third_party_app_id(&self) -> &str {
+        &self.third_party_app_id
+    }
+}
+
+#[derive(Serialize)]
+struct LiveInfoRequest {
+    start: u32,
+    limit: u32,
+}
+
+#[derive(Serialize)]
+struct ReplayRequest {
+    action: &'static str,
+    room_id: i64,
+    start: u32,
+    limit: u32,
+}
+
+/// 直播回放列表
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReplayList {
+    #[serde(default)]
+    live_replay: Vec<Replay>,
+    #[serde(default)]
+    total: u64,
+}
+
+impl ReplayList {
+    pub fn live_replay(&self) -> &[Replay] {
+        &self.live_replay
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// 直播回放
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Replay {
+    expire_time: String,
+    create_time: String,
+    media_url: String,
+}
+
+impl Replay {
+    /// 回放视频 url 过期时间
+    pub fn expire_time(&self) -> &str {
+        &self.expire_time
+    }
+
+    /// 回放视频创建时间
+    pub fn create_time(&self) -> &str {
+        &self.create_time
+    }
+
+    /// 回放视频链接
+    pub fn media_url(&self) -> &str {
+        &self.media_url
+    }
+}
+
+#[derive(Serialize)]
+struct RoomRequest {
+    #[serde(rename = "roomId")]
+    room_id: i64,
+}
+
+#[derive(Serialize)]
+struct DeleteRoomRequest {
+    id: i64,
+}
+
+#[derive(Serialize)]
+struct AddGoodsRequest<'a> {
+    ids: &'a [i64],
+    #[serde(rename = "roomId")]
+    room_id: i64,
+}
+
+/// 直播间小助手
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct LiveAssistant {
+    /// 用户微信号
+    pub username: String,
+    /// 用户微信昵称
+    pub nickname: String,
+}
+
+#[derive(Serialize)]
+struct AddAssistantRequest<'a> {
+    #[serde(rename = "roomId")]
+    room_id: i64,
+    users: &'a [LiveAssistant],
+}
+
+#[derive(Serialize)]
+struct AssistantRequest<'a> {
+    #[serde(rename = "roomId")]
+    room_id: i64,
+    username: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<&'a str>,
+}
+
+/// 直播间小助手列表
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssistantList {
+    #[serde(default)]
+    list: Vec<Assistant>,
+    #[serde(default)]
+    count: u32,
+    #[serde(rename(deserialize = "maxCount"), default)]
+    max_count: u32,
+}
+
+impl AssistantList {
+    pub fn list(&self) -> &[Assistant] {
+        &self.list
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// 小助手个数上限
+    pub fn max_count(&self) -> u32 {
+        self.max_count
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Assistant {
+    #[serde(default)]
+    timestamp: i64,
+    #[serde(default)]
+    headimg: String,
+    #[serde(default)]
+    nickname: String,
+    #[serde(default)]
+    alias: String,
+    #[serde(
+        rename(deserialize = "openid"),
+        default,
+        deserialize_with = "OpenId::deserialize_optional"
+    )]
+    open_id: Option<OpenId>,
+}
+
+impl Assistant {
+    /// 修改时间
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// 头像
+    pub fn headimg(&self) -> &str {
+        &self.headimg
+    }
+
+    pub fn nickname(&self) -> &str {
+        &self.nickname
+    }
+
+    /// 微信号
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    pub fn open_id(&self) -> Option<&OpenId> {
+        self.open_id.as_ref()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SubAnchorBuilder {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushUrlBuilder {
+    #[serde(rename = "pushAddr")]
+    push_addr: String,
+}
+
+#[derive(Serialize)]
+struct SharedCodeQuery<'a> {
+    #[serde(rename = "roomId")]
+    room_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<&'a str>,
+}
+
+/// 直播间分享二维码
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharedCode {
+    #[serde(rename(deserialize = "cdnUrl"))]
+    cdn_url: String,
+    #[serde(rename(deserialize = "pagePath"))]
+    page_path: String,
+    #[serde(rename(deserialize = "posterUrl"), default)]
+    poster_url: String,
+}
+
+impl SharedCode {
+    /// 分享二维码
+    pub fn cdn_url(&self) -> &str {
+        &self.cdn_url
+    }
+
+    /// 分享路径
+    pub fn page_path(&self) -> &str {
+        &self.page_path
+    }
+
+    /// 分享海报
+    pub fn poster_url(&self) -> &str {
+        &self.poster_url
+    }
+}
+
+/// 小程序直播间管理接口
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{credential::GenericAccessToken, live::ManageLive, Result};
+/// use serde_json::json;
+///
+/// pub(crate) async fn rooms(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let rooms = access_token.get_live_info(0, 10).await?;
+///
+///     Ok(Json(json!({
+///         "rooms": rooms,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait ManageLive {
+    const CREATE_ROOM: &'static str = "https://api.weixin.qq.com/wxaapi/broadcast/room/create";
+    const DELETE_ROOM: &'static str = "https://api.weixin.qq.com/wxaapi/broadcast/room/deleteroom";
+    const LIVE_INFO: &'static str = "https://api.weixin.qq.com/wxa/business/getliveinfo";
+    const ADD_GOODS: &'static str = "https://api.weixin.qq.com/wxaapi/broadcast/room/addgoods";
+    const ADD_ASSISTANT: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/addassistant";
+    const MODIFY_ASSISTANT: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/modifyassistant";
+    const REMOVE_ASSISTANT: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/removeassistant";
+    const ASSISTANT_LIST: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/getassistantlist";
+    const ADD_SUB_ANCHOR: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/addsubanchor";
+    const MODIFY_SUB_ANCHOR: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/modifysubanchor";
+    const DELETE_SUB_ANCHOR: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/deletesubanchor";
+    const SUB_ANCHOR: &'static str = "https://api.weixin.qq.com/wxaapi/broadcast/room/getsubanchor";
+    const PUSH_URL: &'static str = "https://api.weixin.qq.com/wxaapi/broadcast/room/getpushurl";
+    const SHARED_CODE: &'static str =
+        "https://api.weixin.qq.com/wxaapi/broadcast/room/getsharedcode";
+
+    /// 创建直播间
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/createRoom.html
+    async fn create_room(&self, room: &LiveRoom) -> Result<CreatedRoom>;
+
+    /// 删除直播间
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/deleteRoom.html
+    async fn delete_room(&self, room_id: i64) -> Result<()>;
+
+    /// 获取直播间列表，`limit` 最大为 100
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/getLiveInfo.html
+    async fn get_live_info(&self, start: u32, limit: u32) -> Result<LiveRoomList>;
+
+    /// 获取直播间回放
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/getLiveInfo.html
+    async fn get_replay(&self, room_id: i64, start: u32, limit: u32) -> Result<ReplayList>;
+
+    /// 往直播间导入已入库的商品
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/goodsOnsale.html
+    async fn add_goods(&self, room_id: i64, goods_ids: &[i64]) -> Result<()>;
+
+    /// 添加直播间小助手
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/addAssistant.html
+    async fn add_assistant(&self, room_id: i64, users: &[LiveAssistant]) -> Result<()>;
+
+    /// 修改直播间小助手昵称
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/modifyAssistant.html
+    async fn modify_assistant(&self, room_id: i64, username: &str, nickname: &str) -> Result<()>;
+
+    /// 删除直播间小助手
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/removeAssistant.html
+    async fn remove_assistant(&self, room_id: i64, username: &str) -> Result<()>;
+
+    /// 查询直播间小助手
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/getAssistantList.html
+    async fn get_assistant_list(&self, room_id: i64) -> Result<AssistantList>;
+
+    /// 添加主播副号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/addSubanchor.html
+    async fn add_sub_anchor(&self, room_id: i64, username: &str) -> Result<()>;
+
+    /// 修改主播副号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/modifySubanchor.html
+    async fn modify_sub_anchor(&self, room_id: i64, username: &str) -> Result<()>;
+
+    /// 删除主播副号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/deleteSubanchor.html
+    async fn delete_sub_anchor(&self, room_id: i64) -> Result<()>;
+
+    /// 获取主播副号的微信号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/getSubanchor.html
+    async fn get_sub_anchor(&self, room_id: i64) -> Result<String>;
+
+    /// 获取直播间推流地址
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/getPushUrl.html
+    async fn get_push_url(&self, room_id: i64) -> Result<String>;
+
+    /// 获取直播间分享二维码，`params` 为自定义参数
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/livebroadcast/studio-management/getSharedCode.html
+    async fn get_shared_code(&self, room_id: i64, params: Option<&str>) -> Result<SharedCode>;
+}
+
+#[async_trait]
+impl<T> ManageLive for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, room))]
+    async fn create_room(&self, room: &LiveRoom) -> Result<CreatedRoom> {
+        let created = self
+            .client()
+            .post::<_, CreatedRoom>(Self::CREATE_ROOM, &self.token().await?, room)
+            .await?;
+
+        event!(Level::DEBUG, "created room: {:#?}", created);
+
+        Ok(created)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_room(&self, room_id: i64) -> Result<()> {
+        self.client()
+            .post(
+                Self::DELETE_ROOM,
+                &self.token().await?,
+                &DeleteRoomRequest { id: room_id },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_live_info(&self, start: u32, limit: u32) -> Result<LiveRoomList> {
+        self.client()
+            .post(
+                Self::LIVE_INFO,
+                &self.token().await?,
+                &LiveInfoRequest { start, limit },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_replay(&self, room_id: i64, start: u32, limit: u32) -> Result<ReplayList> {
+        let request = ReplayRequest {
+            action: "get_replay",
+            room_id,
+            start,
+            limit,
+        };
+
+        self.client()
+            .post(Self::LIVE_INFO, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn add_goods(&self, room_id: i64, goods_ids: &[i64]) -> Result<()> {
+        self.client()
+            .post(
+                Self::ADD_GOODS,
+                &self.token().await?,
+                &AddGoodsRequest {
+                    ids: goods_ids,
+                    room_id,
+                },
+            )
+            .await
+    }
+
+    #[instrument(skip(self, users))]
+    async fn add_assistant(&self, room_id: i64, users: &[LiveAssistant]) -> Result<()> {
+        self.client()
+            .post(
+                Self::ADD_ASSISTANT,
+                &self.token().await?,
+                &AddAssistantRequest { room_id, users },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn modify_assistant(&self, room_id: i64, username: &str, nickname: &str) -> Result<()> {
+        let request = AssistantRequest {
+            room_id,
+            username,
+            nickname: Some(nickname),
+        };
+
+        self.client()
+            .post(Self::MODIFY_ASSISTANT, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn remove_assistant(&self, room_id: i64, username: &str) -> Result<()> {
+        let request = AssistantRequest {
+            room_id,
+            username,
+            nickname: None,
+        };
+
+        self.client()
+            .post(Self::REMOVE_ASSISTANT, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_assistant_list(&self, room_id: i64) -> Result<AssistantList> {
+        self.client()
+            .get(
+                Self::ASSISTANT_LIST,
+                &self.token().await?,
+                &RoomRequest { room_id },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn add_sub_anchor(&self, room_id: i64, username: &str) -> Result<()> {
+        let request = AssistantRequest {
+            room_id,
+            username,
+            nickname: None,
+        };
+
+        self.client()
+            .post(Self::ADD_SUB_ANCHOR, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn modify_sub_anchor(&self, room_id: i64, username: &str) -> Result<()> {
+        let request = AssistantRequest {
+            room_id,
+            username,
+            nickname: None,
+        };
+
+        self.client()
+            .post(Self::MODIFY_SUB_ANCHOR, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_sub_anchor(&self, room_id: i64) -> Result<()> {
+        self.client()
+            .post(
+                Self::DELETE_SUB_ANCHOR,
+                &self.token().await?,
+                &RoomRequest { room_id },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_sub_anchor(&self, room_id: i64) -> Result<String> {
+        let builder = self
+            .client()
+            .get::<_, SubAnchorBuilder>(
+                Self::SUB_ANCHOR,
+                &self.token().await?,
+                &RoomRequest { room_id },
+            )
+            .await?;
+
+        Ok(builder.username)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_push_url(&self, room_id: i64) -> Result<String> {
+        let builder = self
+            .client()
+            .get::<_, PushUrlBuilder>(
+                Self::PUSH_URL,
+                &self.token().await?,
+                &RoomRequest { room_id },
+            )
+            .await?;
+
+        Ok(builder.push_addr)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_shared_code(&self, room_id: i64, params: Option<&str>) -> Result<SharedCode> {
+        self.client()
+            .get(
+                Self::SHARED_CODE,
+                &self.token().await?,
+                &SharedCodeQuery { room_id, params },
+            )
+            .await
+    }
+}
@@ -0,0 +1,1665 @@
+use async_trait::async_trait;
+use hex::encode;
+use serde::{Deserialize, Serialize};
+use serde_repr::Serialize_repr;
+use sha1::{Digest, Sha1};
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, id::OpenId, Result};
+
+/// 快递公司
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Delivery {
+    delivery_id: String,
+    delivery_name: String,
+    #[serde(default)]
+    can_use_cash: u8,
+    #[serde(default)]
+    can_get_quota: u8,
+    #[serde(default)]
+    cash_biz_id: String,
+    #[serde(default)]
+    service_type: Vec<ExpressService>,
+}
+
+impl Delivery {
+    /// 快递公司 ID
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    pub fn delivery_name(&self) -> &str {
+        &self.delivery_name
+    }
+
+    /// 是否支持散单
+    pub fn can_use_cash(&self) -> bool {
+        self.can_use_cash == 1
+    }
+
+    /// 是否支持查询面单余额
+    pub fn can_get_quota(&self) -> bool {
+        self.can_get_quota == 1
+    }
+
+    /// 散单对应的 biz_id
+    pub fn cash_biz_id(&self) -> &str {
+        &self.cash_biz_id
+    }
+
+    /// 支持的服务类型
+    pub fn service_type(&self) -> &[ExpressService] {
+        &self.service_type
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DeliveryBuilder {
+    #[serde(default)]
+    data: Vec<Delivery>,
+}
+
+/// 快递服务类型
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ExpressService {
+    pub service_type: i64,
+    pub service_name: String,
+}
+
+/// 订单来源
+#[derive(Debug, Serialize_repr, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum AddSource {
+    /// 小程序订单
+    #[default]
+    MiniProgram = 0,
+    /// App 或 H5 订单
+    App = 2,
+}
+
+/// 发件人或收件人信息，`tel` 和 `mobile` 二选一
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ExpressContact {
+    pub name: String,
+    /// 座机号码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tel: Option<String>,
+    /// 手机号码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mobile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    pub province: String,
+    pub city: String,
+    pub area: String,
+    /// 详细地址
+    pub address: String,
+}
+
+/// 包裹信息
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Cargo {
+    /// 包裹数量
+    pub count: u32,
+    /// 包裹总重量，单位是千克
+    pub weight: f64,
+    /// 包裹长度，单位厘米
+    pub space_x: f64,
+    /// 包裹宽度，单位厘米
+    pub space_y: f64,
+    /// 包裹高度，单位厘米
+    pub space_z: f64,
+    pub detail_list: Vec<CargoDetail>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CargoDetail {
+    pub name: String,
+    pub count: u32,
+}
+
+/// 商品信息，会展示到物流服务通知和电子面单中
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ExpressShop {
+    /// 商家小程序的路径，建议为订单页面
+    pub wxa_path: String,
+    /// 商品缩略图 url
+    pub img_url: String,
+    /// 商品名称，不超过 128 字节
+    pub goods_name: String,
+    pub goods_count: u32,
+}
+
+/// 保价信息
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Insured {
+    /// 是否保价，0 表示不保价，1 表示保价
+    pub use_insured: u8,
+    /// 保价金额，单位是分
+    pub insured_value: u64,
+}
+
+/// 生成运单的参数
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ExpressOrder {
+    pub add_source: AddSource,
+    /// App 或 H5 的 appid，`add_source` 为 [`AddSource::App`] 时必填
+    #[serde(rename = "wx_appid", skip_serializing_if = "Option::is_none")]
+    pub wx_app_id: Option<String>,
+    /// 订单 ID，须保证全局唯一，不超过 512 字节
+    pub order_id: String,
+    /// 用户 openid，`add_source` 为 [`AddSource::MiniProgram`] 时必填
+    #[serde(rename = "openid", skip_serializing_if = "Option::is_none")]
+    pub open_id: Option<OpenId>,
+    pub delivery_id: String,
+    /// 快递客户编码或者现付编码
+    pub biz_id: String,
+    /// 快递备注信息，会打印到面单上
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_remark: Option<String>,
+    /// 订单标签 id，用于平台型小程序区分平台上的入驻方
+    #[serde(rename = "tagid", skip_serializing_if = "Option::is_none")]
+    pub tag_id: Option<u64>,
+    pub sender: ExpressContact,
+    pub receiver: ExpressContact,
+    pub cargo: Cargo,
+    pub shop: ExpressShop,
+    pub insured: Insured,
+    pub service: ExpressService,
+    /// 预期的上门揽件时间，秒级时间戳，仅部分快递公司支持
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_time: Option<i64>,
+}
+
+/// 运单信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WaybillData {
+    key: String,
+    value: String,
+}
+
+impl WaybillData {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// 生成运单的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpressOrderResult {
+    order_id: String,
+    #[serde(default)]
+    waybill_id: String,
+    #[serde(default)]
+    waybill_data: Vec<WaybillData>,
+    #[serde(default)]
+    delivery_resultcode: i64,
+    #[serde(default)]
+    delivery_resultmsg: String,
+}
+
+impl ExpressOrderResult {
+    pub fn order_id(&self) -> &str {
+        &self.order_id
+    }
+
+    /// 运单 ID
+    pub fn waybill_id(&self) -> &str {
+        &self.waybill_id
+    }
+
+    pub fn waybill_data(&self) -> &[WaybillData] {
+        &self.waybill_data
+    }
+
+    /// 快递公司侧错误码
+    pub fn delivery_resultcode(&self) -> i64 {
+        self.delivery_resultcode
+    }
+
+    /// 快递公司侧错误信息
+    pub fn delivery_resultmsg(&self) -> &str {
+        &self.delivery_resultmsg
+    }
+}
+
+/// 运单，用于取消运单、查询运单和查询运单轨迹
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ExpressOrderKey {
+    pub order_id: String,
+    /// 用户 openid，`add_source` 为 [`AddSource::MiniProgram`] 时必填
+    #[serde(rename = "openid", skip_serializing_if = "Option::is_none")]
+    pub open_id: Option<OpenId>,
+    pub delivery_id: String,
+    pub waybill_id: String,
+}
+
+#[derive(Serialize)]
+struct ExpressOrderQuery<'a> {
+    #[serde(flatten)]
+    key: &'a ExpressOrderKey,
+    print_type: u8,
+}
+
+/// 取消运单的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CancelResult {
+    #[serde(default)]
+    delivery_resultcode: i64,
+    #[serde(default)]
+    delivery_resultmsg: String,
+}
+
+impl CancelResult {
+    /// 快递公司侧错误码
+    pub fn delivery_resultcode(&self) -> i64 {
+        self.delivery_resultcode
+    }
+
+    /// 快递公司侧错误信息
+    pub fn delivery_resultmsg(&self) -> &str {
+        &self.delivery_resultmsg
+    }
+}
+
+/// 运单详情
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpressOrderDetail {
+    #[serde(default)]
+    print_html: String,
+    #[serde(default)]
+    waybill_data: Vec<WaybillData>,
+    delivery_id: String,
+    order_id: String,
+    waybill_id: String,
+    #[serde(default)]
+    order_status: i64,
+}
+
+impl ExpressOrderDetail {
+    /// Base64 编码的运单 html，可用于打印面单
+    pub fn print_html(&self) -> &str {
+        &self.print_html
+    }
+
+    pub fn waybill_data(&self) -> &[WaybillData] {
+        &self.waybill_data
+    }
+
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    pub fn order_id(&self) -> &str {
+        &self.order_id
+    }
+
+    pub fn waybill_id(&self) -> &str {
+        &self.waybill_id
+    }
+
+    /// 运单状态，0 正常，1 取消
+    pub fn order_status(&self) -> i64 {
+        self.order_status
+    }
+}
+
+/// 运单轨迹
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpressPath {
+    #[serde(
+        rename(deserialize = "openid"),
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    open_id: Option<OpenId>,
+    delivery_id: String,
+    waybill_id: String,
+    #[serde(default)]
+    path_item_num: u32,
+    #[serde(default)]
+    path_item_list: Vec<PathItem>,
+}
+
+impl ExpressPath {
+    pub fn open_id(&self) -> Option<&OpenId> {
+        self.open_id.as_ref()
+    }
+
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    pub fn waybill_id(&self) -> &str {
+        &self.waybill_id
+    }
+
+    /// 轨迹节点数量
+    pub fn path_item_num(&self) -> u32 {
+        self.path_item_num
+    }
+
+    pub fn path_item_list(&self) -> &[PathItem] {
+        &self.path_item_list
+    }
+}
+
+/// 轨迹节点
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PathItem {
+    action_time: i64,
+    action_type: u32,
+    action_msg: String,
+}
+
+impl PathItem {
+    /// 轨迹节点 Unix 时间戳
+    pub fn action_time(&self) -> i64 {
+        self.action_time
+    }
+
+    /// 轨迹节点类型，如 100001 揽件阶段-揽件成功，300003 签收阶段-签收成功
+    pub fn action_type(&self) -> u32 {
+        self.action_type
+    }
+
+    /// 轨迹节点详情
+    pub fn action_msg(&self) -> &str {
+        &self.action_msg
+    }
+}
+
+/// 绑定或解绑
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BindType {
+    Bind,
+    Unbind,
+}
+
+/// 绑定、解绑物流账号的参数
+#[derive(Debug, Serialize, Clone)]
+pub struct BindAccount {
+    #[serde(rename = "type")]
+    pub bind_type: BindType,
+    /// 快递公司客户编码
+    pub biz_id: String,
+    pub delivery_id: String,
+    /// 快递公司客户密码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// 审核信息，部分快递公司需要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remark_content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountBuilder {
+    #[serde(default)]
+    list: Vec<ExpressAccount>,
+}
+
+/// 已绑定的物流账号
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpressAccount {
+    biz_id: String,
+    delivery_id: String,
+    #[serde(default)]
+    create_time: i64,
+    #[serde(default)]
+    update_time: i64,
+    #[serde(default)]
+    status_code: i64,
+    #[serde(default)]
+    alias: String,
+    #[serde(default)]
+    remark_wrong_msg: String,
+    #[serde(default)]
+    remark_content: String,
+    #[serde(default)]
+    quota_num: i64,
+    #[serde(default)]
+    quota_update_time: i64,
+    #[serde(default)]
+    service_type: Vec<ExpressService>,
+}
+
+impl ExpressAccount {
+    pub fn biz_id(&self) -> &str {
+        &self.biz_id
+    }
+
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    pub fn create_time(&self) -> i64 {
+        self.create_time
+    }
+
+    pub fn update_time(&self) -> i64 {
+        self.update_time
+    }
+
+    /// 绑定状态，0 为已绑定
+    pub fn status_code(&self) -> i64 {
+        self.status_code
+    }
+
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// 账号绑定失败的错误信息
+    pub fn remark_wrong_msg(&self) -> &str {
+        &self.remark_wrong_msg
+    }
+
+    pub fn remark_content(&self) -> &str {
+        &self.remark_content
+    }
+
+    /// 电子面单余额
+    pub fn quota_num(&self) -> i64 {
+        self.quota_num
+    }
+
+    pub fn quota_update_time(&self) -> i64 {
+        self.quota_update_time
+    }
+
+    pub fn service_type(&self) -> &[ExpressService] {
+        &self.service_type
+    }
+}
+
+#[derive(Serialize)]
+struct PrinterRequest<'a> {
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+    update_type: BindType,
+    #[serde(rename = "tagid_list", skip_serializing_if = "Option::is_none")]
+    tag_id_list: Option<&'a str>,
+}
+
+/// 面单打印员
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Printers {
+    #[serde(default)]
+    count: u32,
+    #[serde(rename(deserialize = "openid"), default)]
+    open_ids: Vec<OpenId>,
+    #[serde(rename(deserialize = "tagid_list"), default)]
+    tag_id_list: Vec<String>,
+}
+
+impl Printers {
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// 打印员 openid 列表
+    pub fn open_ids(&self) -> &[OpenId] {
+        &self.open_ids
+    }
+
+    /// 打印员面单打印权限
+    pub fn tag_id_list(&self) -> &[String] {
+        &self.tag_id_list
+    }
+}
+
+/// 物流服务通知中展示的商品
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TraceGoods {
+    pub goods_name: String,
+    pub goods_img_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_desc: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TraceGoodsInfo {
+    pub detail_list: Vec<TraceGoods>,
+}
+
+/// 传运单接口的参数，用于在微信中展示物流轨迹
+#[derive(Debug, Serialize, Clone)]
+pub struct TraceWaybill {
+    #[serde(rename = "openid")]
+    pub open_id: OpenId,
+    /// 收件人手机号
+    pub receiver_phone: String,
+    pub waybill_id: String,
+    pub goods_info: TraceGoodsInfo,
+    /// 交易单号，微信支付生成的交易单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trans_id: Option<String>,
+    /// 点击物流详情页的商品跳转的小程序路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_detail_path: Option<String>,
+    /// 快递公司 ID，不填时由微信识别
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaybillTokenBuilder {
+    waybill_token: String,
+}
+
+#[derive(Serialize)]
+struct WaybillTokenRequest<'a> {
+    waybill_token: &'a str,
+}
+
+/// 查询运单详情的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpressTrace {
+    waybill_info: TraceWaybillInfo,
+    #[serde(default)]
+    shop_info: Option<TraceShopInfo>,
+    #[serde(default)]
+    delivery_info: Option<TraceDeliveryInfo>,
+    #[serde(default)]
+    path_info: Option<TracePathInfo>,
+}
+
+impl ExpressTrace {
+    pub fn waybill_id(&self) -> &str {
+        &self.waybill_info.waybill_id
+    }
+
+    /// 运单状态，0 已揽件，2 运输中，3 派件中，4 已签收，5 异常，6 代签收
+    pub fn status(&self) -> i64 {
+        self.waybill_info.status
+    }
+
+    pub fn goods(&self) -> &[TraceGoods] {
+        self.shop_info
+            .as_ref()
+            .map(|shop| shop.goods_info.detail_list.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn delivery_id(&self) -> Option<&str> {
+        self.delivery_info
+            .as_ref()
+            .map(|delivery| delivery.delivery_id.as_str())
+    }
+
+    pub fn delivery_name(&self) -> Option<&str> {
+        self.delivery_info
+            .as_ref()
+            .map(|delivery| delivery.delivery_name.as_str())
+    }
+
+    pub fn path_item_list(&self) -> &[PathItem] {
+        self.path_info
+            .as_ref()
+            .map(|path| path.path_item_list.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct TraceWaybillInfo {
+    waybill_id: String,
+    #[serde(default)]
+    status: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct TraceShopInfo {
+    goods_info: TraceGoodsInfo,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct TraceDeliveryInfo {
+    delivery_id: String,
+    #[serde(default)]
+    delivery_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct TracePathInfo {
+    #[serde(default)]
+    path_item_list: Vec<PathItem>,
+}
+
+/// 物流助手接口，包括面向商家的电子面单和面向用户的物流轨迹查询
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{
+///     credential::GenericAccessToken,
+///     id::OpenId,
+///     logistics::{ExpressOrderKey, ManageLogistics},
+///     Result,
+/// };
+/// use serde_json::json;
+///
+/// pub(crate) async fn path(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let key = ExpressOrderKey {
+///         order_id: "01234567890123456789".into(),
+///         open_id: Some(OpenId::new("oUpF8uMuAJO_M2pxb1Q9zNjWeS6o")?),
+///         delivery_id: "SF".into(),
+///         waybill_id: "123456789".into(),
+///     };
+///
+///     let path = access_token.get_path(&key).await?;
+///
+///     Ok(Json(json!({
+///         "path": path,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait ManageLogistics {
+    const ALL_DELIVERY: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/business/delivery/getall";
+    const ADD_ORDER: &'static str = "https://api.weixin.qq.com/cgi-bin/express/business/order/add";
+    const CANCEL_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/business/order/cancel";
+    const GET_ORDER: &'static str = "https://api.weixin.qq.com/cgi-bin/express/business/order/get";
+    const GET_PATH: &'static str = "https://api.weixin.qq.com/cgi-bin/express/business/path/get";
+    const BIND_ACCOUNT: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/business/account/bind";
+    const ALL_ACCOUNT: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/business/account/getall";
+    const UPDATE_PRINTER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/business/printer/update";
+    const ALL_PRINTER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/business/printer/getall";
+    const TRACE_WAYBILL: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/delivery/open_msg/trace_waybill";
+    const QUERY_TRACE: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/delivery/open_msg/query_trace";
+
+    /// 获取支持的快递公司列表
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/getAllDelivery.html
+    async fn get_all_delivery(&self) -> Result<Vec<Delivery>>;
+
+    /// 生成运单
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/addOrder.html
+    async fn add_order(&self, order: &ExpressOrder) -> Result<ExpressOrderResult>;
+
+    /// 取消运单
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/cancelOrder.html
+    async fn cancel_order(&self, key: &ExpressOrderKey) -> Result<CancelResult>;
+
+    /// 获取运单数据，`print` 为 true 时返回可打印面单的 html
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/getOrder.html
+    async fn get_order(&self, key: &ExpressOrderKey, print: bool) -> Result<ExpressOrderDetail>;
+
+    /// 查询运单轨迹
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/getPath.html
+    async fn get_path(&self, key: &ExpressOrderKey) -> Result<ExpressPath>;
+
+    /// 绑定、解绑物流账号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/bindAccount.html
+    async fn bind_account(&self, account: &BindAccount) -> Result<()>;
+
+    /// 获取所有绑定的物流账号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/getAllAccount.html
+    async fn get_all_account(&self) -> Result<Vec<ExpressAccount>>;
+
+    /// 配置面单打印员，`tag_id_list` 为打印员的面单打印权限，多个以英文逗号分隔
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/updatePrinter.html
+    async fn update_printer(
+        &self,
+        open_id: &OpenId,
+        update_type: BindType,
+        tag_id_list: Option<&str>,
+    ) -> Result<()>;
+
+    /// 获取面单打印员
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-by-business/getPrinter.html
+    async fn get_printer(&self) -> Result<Printers>;
+
+    /// 传运单，返回用于查询运单详情的 waybill_token
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-open-msg/traceWaybill.html
+    async fn trace_waybill(&self, waybill: &TraceWaybill) -> Result<String>;
+
+    /// 使用 waybill_token 查询运单详情
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/express/express-open-msg/queryTrace.html
+    async fn query_trace(&self, waybill_token: &str) -> Result<ExpressTrace>;
+}
+
+#[async_trait]
+impl<T> ManageLogistics for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self))]
+    async fn get_all_delivery(&self) -> Result<Vec<Delivery>> {
+        let builder = self
+            .client()
+            .get::<_, DeliveryBuilder>(Self::ALL_DELIVERY, &self.token().await?, &())
+            .await?;
+
+        Ok(builder.data)
+    }
+
+    #[instrument(skip(self, order))]
+    async fn add_order(&self, order: &ExpressOrder) -> Result<ExpressOrderResult> {
+        let result = self
+            .client()
+            .post::<_, ExpressOrderResult>(Self::ADD_ORDER, &self.token().await?, order)
+            .await?;
+
+        event!(Level::DEBUG, "express order: {:#?}", result);
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    async fn cancel_order(&self, key: &ExpressOrderKey) -> Result<CancelResult> {
+        self.client()
+            .post(Self::CANCEL_ORDER, &self.token().await?, key)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_order(&self, key: &ExpressOrderKey, print: bool) -> Result<ExpressOrderDetail> {
+        let query = ExpressOrderQuery {
+            key,
+            print_type: print.into(),
+        };
+
+        self.client()
+            .post(Self::GET_ORDER, &self.token().await?, &query)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_path(&self, key: &ExpressOrderKey) -> Result<ExpressPath> {
+        self.client()
+            .post(Self::GET_PATH, &self.token().await?, key)
+            .await
+    }
+
+    #[instrument(skip(self, account))]
+    async fn bind_account(&self, account: &BindAccount) -> Result<()> {
+        self.client()
+            .post(Self::BIND_ACCOUNT, &self.token().await?, account)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_all_account(&self) -> Result<Vec<ExpressAccount>> {
+        let builder = self
+            .client()
+            .get::<_, AccountBuilder>(Self::ALL_ACCOUNT, &self.token().await?, &())
+            .await?;
+
+        Ok(builder.list)
+    }
+
+    #[instrument(skip(self))]
+    async fn update_printer(
+        &self,
+        open_id: &OpenId,
+        update_type: BindType,
+        tag_id_list: Option<&str>,
+    ) -> Result<()> {
+        let request = PrinterRequest {
+            open_id: open_id.as_str(),
+            update_type,
+            tag_id_list,
+        };
+
+        self.client()
+            .post(Self::UPDATE_PRINTER, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_printer(&self) -> Result<Printers> {
+        self.client()
+            .get(Self::ALL_PRINTER, &self.token().await?, &())
+            .await
+    }
+
+    #[instrument(skip(self, waybill))]
+    async fn trace_waybill(&self, waybill: &TraceWaybill) -> Result<String> {
+        let builder = self
+            .client()
+            .post::<_, WaybillTokenBuilder>(Self::TRACE_WAYBILL, &self.token().await?, waybill)
+            .await?;
+
+        Ok(builder.waybill_token)
+    }
+
+    #[instrument(skip(self))]
+    async fn query_trace(&self, waybill_token: &str) -> Result<ExpressTrace> {
+        self.client()
+            .post(
+                Self::QUERY_TRACE,
+                &self.token().await?,
+                &WaybillTokenRequest { waybill_token },
+            )
+            .await
+    }
+}
+
+/// 即时配送公司
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImmediateDelivery {
+    delivery_id: String,
+    delivery_name: String,
+}
+
+impl ImmediateDelivery {
+    /// 配送公司 ID
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    pub fn delivery_name(&self) -> &str {
+        &self.delivery_name
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ImmediateDeliveryBuilder {
+    #[serde(default)]
+    list: Vec<ImmediateDelivery>,
+}
+
+/// 已绑定的即时配送账号
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalAccount {
+    delivery_id: String,
+    #[serde(rename = "shopid")]
+    shop_id: String,
+    #[serde(default)]
+    audit_result: i64,
+}
+
+impl LocalAccount {
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    /// 商家在配送公司的 appkey
+    pub fn shop_id(&self) -> &str {
+        &self.shop_id
+    }
+
+    /// 审核状态，0 审核通过，1 审核中，2 审核不通过
+    pub fn audit_result(&self) -> i64 {
+        self.audit_result
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalAccountBuilder {
+    #[serde(default)]
+    shop_list: Vec<LocalAccount>,
+}
+
+/// 即时配送接口要求请求体为 JSON 对象，没有参数时发送 `{}`
+#[derive(Serialize)]
+struct EmptyRequest {}
+
+#[derive(Serialize)]
+struct LocalDeliveryRequest<'a> {
+    delivery_id: &'a str,
+}
+
+/// 配送公司侧的处理结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalResult {
+    #[serde(default)]
+    resultcode: i64,
+    #[serde(default)]
+    resultmsg: String,
+}
+
+impl LocalResult {
+    /// 配送公司侧错误码
+    pub fn resultcode(&self) -> i64 {
+        self.resultcode
+    }
+
+    /// 配送公司侧错误信息
+    pub fn resultmsg(&self) -> &str {
+        &self.resultmsg
+    }
+}
+
+/// 计算即时配送的 `delivery_sign`，即 `sha1(shopid + shop_order_id + AppSecret)`，
+/// 其中 `app_secret` 为配送公司分配的 appsecret
+/// ```rust
+/// use open_wechat::logistics::delivery_sign;
+///
+/// let sign = delivery_sign("122222", "123456789", "your delivery app secret");
+///
+/// assert_eq!(sign, "3ec403270d9c487a1a730eaea401f49ffb37195f");
+/// ```
+pub fn delivery_sign(shop_id: &str, shop_order_id: &str, app_secret: &str) -> String {
+    encode(Sha1::digest(
+        format!("{shop_id}{shop_order_id}{app_secret}").as_bytes(),
+    ))
+}
+
+/// 即时配送订单，用于查询、取消订单和增加小费等
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct LocalOrderKey {
+    /// 商家在配送公司的 appkey
+    #[serde(rename = "shopid")]
+    pub shop_id: String,
+    /// 商家订单号
+    pub shop_order_id: String,
+    /// 商家门店编号，在配送公司登记
+    pub shop_no: String,
+    /// 见 [`delivery_sign`]
+    pub delivery_sign: String,
+}
+
+/// 即时配送的发件人或收件人
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LocalContact {
+    pub name: String,
+    pub city: String,
+    pub address: String,
+    /// 详细地址，如门牌号
+    pub address_detail: String,
+    pub phone: String,
+    /// 经度，火星坐标或百度坐标
+    pub lng: f64,
+    /// 纬度，火星坐标或百度坐标
+    pub lat: f64,
+    /// 坐标类型，0 为火星坐标（高德、腾讯地图），1 为百度坐标
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coordinate_type: Option<u8>,
+}
+
+/// 即时配送的商品
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LocalGoods {
+    #[serde(rename = "good_count")]
+    pub count: u32,
+    #[serde(rename = "good_name")]
+    pub name: String,
+    /// 商品单价，单位是元
+    #[serde(rename = "good_price", skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// 商品单位，如件、斤
+    #[serde(rename = "good_unit", skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LocalGoodsDetail {
+    pub goods: Vec<LocalGoods>,
+}
+
+/// 即时配送的货物信息
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LocalCargo {
+    /// 货物价格，单位是元
+    pub goods_value: f64,
+    /// 货物高度，单位厘米
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_height: Option<f64>,
+    /// 货物长度，单位厘米
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_length: Option<f64>,
+    /// 货物宽度，单位厘米
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_width: Option<f64>,
+    /// 货物重量，单位千克
+    pub goods_weight: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_detail: Option<LocalGoodsDetail>,
+    /// 取货信息，会展示给骑手
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_pickup_info: Option<String>,
+    /// 送货信息，会展示给骑手
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_delivery_info: Option<String>,
+    /// 品类一级类目
+    pub cargo_first_class: String,
+    /// 品类二级类目
+    pub cargo_second_class: String,
+}
+
+/// 即时配送的订单信息
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LocalOrderInfo {
+    /// 配送服务代码，不同配送公司自定义
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_service_code: Option<String>,
+    /// 订单类型，0 即时单，1 预约单
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_type: Option<u8>,
+    /// 期望派单时间，秒级时间戳，预约单必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_delivery_time: Option<i64>,
+    /// 期望送达时间，秒级时间戳
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_finish_time: Option<i64>,
+    /// 期望取件时间，秒级时间戳
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_pick_time: Option<i64>,
+    /// 门店订单流水号，建议提供，方便骑手取货
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poi_seq: Option<String>,
+    /// 备注
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// 用户下单付款时间，秒级时间戳
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_time: Option<i64>,
+    /// 是否保价，0 非保价，1 保价
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_insured: Option<u8>,
+    /// 保价金额，单位是元
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared_value: Option<f64>,
+    /// 小费，单位是元
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tips: Option<f64>,
+    /// 是否选择直拿直送，0 不需要，1 需要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_direct_delivery: Option<u8>,
+    /// 骑手应付金额，单位是元
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cash_on_delivery: Option<f64>,
+    /// 骑手应收金额，单位是元
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cash_on_pickup: Option<f64>,
+    /// 物流流向，1 从门店取件送至用户，2 从用户取件送至门店
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rider_pick_method: Option<u8>,
+    /// 收货码，0 不需要，1 需要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_finish_code_needed: Option<u8>,
+    /// 取货码，0 不需要，1 需要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_pickup_code_needed: Option<u8>,
+}
+
+/// 即时配送的商品信息，会展示到配送服务通知中
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LocalShop {
+    /// 商家小程序的路径，建议为订单页面
+    pub wxa_path: String,
+    /// 商品缩略图 url
+    pub img_url: String,
+    /// 商品名称
+    pub goods_name: String,
+    pub goods_count: u32,
+    /// 若结算方式为第三方平台代付，填写商家小程序的 appid
+    #[serde(rename = "wxa_appid", skip_serializing_if = "Option::is_none")]
+    pub wxa_app_id: Option<String>,
+}
+
+/// 即时配送下单的参数，预下单、下单和重新下单共用
+#[derive(Debug, Serialize, Clone)]
+pub struct LocalOrder {
+    #[serde(flatten)]
+    pub key: LocalOrderKey,
+    pub delivery_id: String,
+    /// 下单用户的 openid
+    #[serde(rename = "openid")]
+    pub open_id: OpenId,
+    pub sender: LocalContact,
+    pub receiver: LocalContact,
+    pub cargo: LocalCargo,
+    pub order_info: LocalOrderInfo,
+    pub shop: LocalShop,
+    /// 子商户 id，区分小程序内部多个子商户
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_biz_id: Option<String>,
+    /// 预下单返回的 `delivery_token`，部分配送公司下单时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_token: Option<String>,
+}
+
+/// 即时配送下单的结果，预下单时没有运单 ID
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalOrderResult {
+    #[serde(default)]
+    resultcode: i64,
+    #[serde(default)]
+    resultmsg: String,
+    #[serde(default)]
+    fee: f64,
+    #[serde(default)]
+    deliverfee: f64,
+    #[serde(default)]
+    couponfee: f64,
+    #[serde(default)]
+    tips: f64,
+    #[serde(default)]
+    insurancefee: f64,
+    #[serde(default)]
+    distance: f64,
+    #[serde(default)]
+    waybill_id: String,
+    #[serde(default)]
+    order_status: i64,
+    #[serde(default)]
+    finish_code: Option<i64>,
+    #[serde(default)]
+    pickup_code: Option<i64>,
+    #[serde(default)]
+    dispatch_duration: i64,
+    #[serde(default)]
+    delivery_token: String,
+}
+
+impl LocalOrderResult {
+    /// 配送公司侧错误码
+    pub fn resultcode(&self) -> i64 {
+        self.resultcode
+    }
+
+    /// 配送公司侧错误信息
+    pub fn resultmsg(&self) -> &str {
+        &self.resultmsg
+    }
+
+    /// 实际运费，单位是元，为运费减去优惠券费用
+    pub fn fee(&self) -> f64 {
+        self.fee
+    }
+
+    /// 运费，单位是元
+    pub fn deliverfee(&self) -> f64 {
+        self.deliverfee
+    }
+
+    /// 优惠券费用，单位是元
+    pub fn couponfee(&self) -> f64 {
+        self.couponfee
+    }
+
+    /// 小费，单位是元
+    pub fn tips(&self) -> f64 {
+        self.tips
+    }
+
+    /// 保价费，单位是元
+    pub fn insurancefee(&self) -> f64 {
+        self.insurancefee
+    }
+
+    /// 配送距离，单位是米
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// 配送单号
+    pub fn waybill_id(&self) -> &str {
+        &self.waybill_id
+    }
+
+    /// 配送状态
+    pub fn order_status(&self) -> i64 {
+        self.order_status
+    }
+
+    /// 收货码
+    pub fn finish_code(&self) -> Option<i64> {
+        self.finish_code
+    }
+
+    /// 取货码
+    pub fn pickup_code(&self) -> Option<i64> {
+        self.pickup_code
+    }
+
+    /// 预计骑手接单时间，单位是秒
+    pub fn dispatch_duration(&self) -> i64 {
+        self.dispatch_duration
+    }
+
+    /// 配送公司可以返回此字段，下单时需要带上
+    pub fn delivery_token(&self) -> &str {
+        &self.delivery_token
+    }
+}
+
+/// 取消即时配送订单的参数
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct LocalCancel {
+    #[serde(flatten)]
+    pub key: LocalOrderKey,
+    pub delivery_id: String,
+    /// 配送单号
+    pub waybill_id: String,
+    /// 取消原因 id，1 暂时不需要邮寄，2 价格不合适，3 订单信息有误，4 其他
+    pub cancel_reason_id: u32,
+    /// 其他取消原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_reason: Option<String>,
+}
+
+/// 取消即时配送订单的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalCancelResult {
+    #[serde(default)]
+    resultcode: i64,
+    #[serde(default)]
+    resultmsg: String,
+    #[serde(default)]
+    deduct_fee: f64,
+    #[serde(default)]
+    desc: String,
+}
+
+impl LocalCancelResult {
+    /// 配送公司侧错误码
+    pub fn resultcode(&self) -> i64 {
+        self.resultcode
+    }
+
+    /// 配送公司侧错误信息
+    pub fn resultmsg(&self) -> &str {
+        &self.resultmsg
+    }
+
+    /// 扣除的违约金，单位是元
+    pub fn deduct_fee(&self) -> f64 {
+        self.deduct_fee
+    }
+
+    /// 说明
+    pub fn desc(&self) -> &str {
+        &self.desc
+    }
+}
+
+/// 即时配送订单详情
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalOrderDetail {
+    #[serde(default)]
+    resultcode: i64,
+    #[serde(default)]
+    resultmsg: String,
+    #[serde(default)]
+    order_status: i64,
+    #[serde(default)]
+    waybill_id: String,
+    #[serde(default)]
+    rider_name: String,
+    #[serde(default)]
+    rider_phone: String,
+    #[serde(default)]
+    rider_lng: f64,
+    #[serde(default)]
+    rider_lat: f64,
+    #[serde(default)]
+    reach_time: i64,
+}
+
+impl LocalOrderDetail {
+    /// 配送公司侧错误码
+    pub fn resultcode(&self) -> i64 {
+        self.resultcode
+    }
+
+    /// 配送公司侧错误信息
+    pub fn resultmsg(&self) -> &str {
+        &self.resultmsg
+    }
+
+    /// 配送状态，如 101 分配骑手，102 骑手到店，201 配送中，302 已送达
+    pub fn order_status(&self) -> i64 {
+        self.order_status
+    }
+
+    /// 配送单号
+    pub fn waybill_id(&self) -> &str {
+        &self.waybill_id
+    }
+
+    pub fn rider_name(&self) -> &str {
+        &self.rider_name
+    }
+
+    pub fn rider_phone(&self) -> &str {
+        &self.rider_phone
+    }
+
+    /// 骑手位置经度，配送中时返回
+    pub fn rider_lng(&self) -> f64 {
+        self.rider_lng
+    }
+
+    /// 骑手位置纬度，配送中时返回
+    pub fn rider_lat(&self) -> f64 {
+        self.rider_lat
+    }
+
+    /// 预计送达时间，秒级时间戳
+    pub fn reach_time(&self) -> i64 {
+        self.reach_time
+    }
+}
+
+/// 增加即时配送订单小费的参数
+#[derive(Debug, Serialize, Clone)]
+pub struct LocalTips {
+    #[serde(flatten)]
+    pub key: LocalOrderKey,
+    /// 配送单号
+    pub waybill_id: String,
+    /// 下单用户的 openid
+    #[serde(rename = "openid")]
+    pub open_id: OpenId,
+    /// 小费金额，单位是元
+    pub tips: f64,
+    /// 备注
+    pub remark: String,
+}
+
+#[derive(Serialize)]
+struct ConfirmReturnRequest<'a> {
+    #[serde(flatten)]
+    key: &'a LocalOrderKey,
+    waybill_id: &'a str,
+    remark: &'a str,
+}
+
+/// 模拟配送公司更新订单状态的参数，`delivery_sign` 仅 [`ManageImmediateDelivery::real_mock_update_order`] 需要
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct MockUpdateOrder {
+    /// 商家在配送公司的 appkey，测试环境固定为 `test_shop_id`
+    #[serde(rename = "shopid")]
+    pub shop_id: String,
+    pub shop_order_id: String,
+    /// 状态变更时间点，秒级时间戳
+    pub action_time: i64,
+    /// 配送状态
+    pub order_status: i64,
+    /// 附加信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_msg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_sign: Option<String>,
+}
+
+/// 即时配送接口，商家通过微信对接多家配送公司，下单后由骑手配送
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{
+///     credential::GenericAccessToken,
+///     logistics::{delivery_sign, LocalOrderKey, ManageImmediateDelivery},
+///     Result,
+/// };
+/// use serde_json::json;
+///
+/// pub(crate) async fn local_order(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let key = LocalOrderKey {
+///         shop_id: "your shop id".into(),
+///         shop_order_id: "01234567890123456789".into(),
+///         shop_no: "your shop no".into(),
+///         delivery_sign: delivery_sign(
+///             "your shop id",
+///             "01234567890123456789",
+///             "your delivery app secret",
+///         ),
+///     };
+///
+///     let order = access_token.get_local_order(&key).await?;
+///
+///     Ok(Json(json!({
+///         "order": order,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait ManageImmediateDelivery {
+    const ALL_IMMEDIATE_DELIVERY: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/delivery/getall";
+    const BIND_LOCAL_ACCOUNT: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/shop/add";
+    const ALL_LOCAL_ACCOUNT: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/shop/get";
+    const OPEN_LOCAL_DELIVERY: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/open";
+    const PRE_ADD_LOCAL_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/pre_add";
+    const ADD_LOCAL_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/add";
+    const READD_LOCAL_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/readd";
+    const PRE_CANCEL_LOCAL_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/precancel";
+    const CANCEL_LOCAL_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/cancel";
+    const GET_LOCAL_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/get";
+    const ADD_LOCAL_TIPS: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/addtips";
+    const CONFIRM_RETURN: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/order/confirm_return";
+    const MOCK_UPDATE_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/test_update_order";
+    const REAL_MOCK_UPDATE_ORDER: &'static str =
+        "https://api.weixin.qq.com/cgi-bin/express/local/business/realmock_update_order";
+
+    /// 获取已支持的配送公司列表
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/getAllImmeDelivery.html
+    async fn get_all_immediate_delivery(&self) -> Result<Vec<ImmediateDelivery>>;
+
+    /// 绑定配送公司账号，拉起配送公司的授权页面
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/bindAccount.html
+    async fn bind_local_account(&self, delivery_id: &str) -> Result<LocalResult>;
+
+    /// 获取已绑定的配送公司账号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/getBindAccount.html
+    async fn get_all_local_account(&self) -> Result<Vec<LocalAccount>>;
+
+    /// 开通即时配送权限
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/openDelivery.html
+    async fn open_local_delivery(&self) -> Result<LocalResult>;
+
+    /// 预下单，查询运费
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/preAddOrder.html
+    async fn pre_add_local_order(&self, order: &LocalOrder) -> Result<LocalOrderResult>;
+
+    /// 下配送单
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/addLocalOrder.html
+    async fn add_local_order(&self, order: &LocalOrder) -> Result<LocalOrderResult>;
+
+    /// 订单取消后重新下单
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/reOrder.html
+    async fn readd_local_order(&self, order: &LocalOrder) -> Result<LocalOrderResult>;
+
+    /// 预取消配送单，查询取消需要扣除的违约金
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/preCancelOrder.html
+    async fn pre_cancel_local_order(&self, cancel: &LocalCancel) -> Result<LocalCancelResult>;
+
+    /// 取消配送单
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/cancelLocalOrder.html
+    async fn cancel_local_order(&self, cancel: &LocalCancel) -> Result<LocalCancelResult>;
+
+    /// 拉取配送单信息
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/getLocalOrder.html
+    async fn get_local_order(&self, key: &LocalOrderKey) -> Result<LocalOrderDetail>;
+
+    /// 增加小费，订单未被骑手接单时可以加小费
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/addTips.html
+    async fn add_local_tips(&self, tips: &LocalTips) -> Result<LocalResult>;
+
+    /// 异常件退回商家时，商家确认收货
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/abnormalConfirm.html
+    async fn confirm_return(
+        &self,
+        key: &LocalOrderKey,
+        waybill_id: &str,
+        remark: &str,
+    ) -> Result<LocalResult>;
+
+    /// 在测试环境中模拟配送公司更新订单状态
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/mockUpdateOrder.html
+    async fn mock_update_order(&self, update: &MockUpdateOrder) -> Result<LocalResult>;
+
+    /// 在正式环境中模拟配送公司更新订单状态，仅部分配送公司支持
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/immediate-delivery/deliver-by-business/realMockUpdateOrder.html
+    async fn real_mock_update_order(&self, update: &MockUpdateOrder) -> Result<LocalResult>;
+}
+
+#[async_trait]
+impl<T> ManageImmediateDelivery for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self))]
+    async fn get_all_immediate_delivery(&self) -> Result<Vec<ImmediateDelivery>> {
+        let builder = self
+            .client()
+            .post::<_, ImmediateDeliveryBuilder>(
+                Self::ALL_IMMEDIATE_DELIVERY,
+                &self.token().await?,
+                &EmptyRequest {},
+            )
+            .await?;
+
+        Ok(builder.list)
+    }
+
+    #[instrument(skip(self))]
+    async fn bind_local_account(&self, delivery_id: &str) -> Result<LocalResult> {
+        self.client()
+            .post(
+                Self::BIND_LOCAL_ACCOUNT,
+                &self.token().await?,
+                &LocalDeliveryRequest { delivery_id },
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_all_local_account(&self) -> Result<Vec<LocalAccount>> {
+        let builder = self
+            .client()
+            .post::<_, LocalAccountBuilder>(
+                Self::ALL_LOCAL_ACCOUNT,
+                &self.token().await?,
+                &EmptyRequest {},
+            )
+            .await?;
+
+        Ok(builder.shop_list)
+    }
+
+    #[instrument(skip(self))]
+    async fn open_local_delivery(&self) -> Result<LocalResult> {
+        self.client()
+            .post(
+                Self::OPEN_LOCAL_DELIVERY,
+                &self.token().await?,
+                &EmptyRequest {},
+            )
+            .await
+    }
+
+    #[instrument(skip(self, order))]
+    async fn pre_add_local_order(&self, order: &LocalOrder) -> Result<LocalOrderResult> {
+        self.client()
+            .post(Self::PRE_ADD_LOCAL_ORDER, &self.token().await?, order)
+            .await
+    }
+
+    #[instrument(skip(self, order))]
+    async fn add_local_order(&self, order: &LocalOrder) -> Result<LocalOrderResult> {
+        let result = self
+            .client()
+            .post::<_, LocalOrderResult>(Self::ADD_LOCAL_ORDER, &self.token().await?, order)
+            .await?;
+
+        event!(Level::DEBUG, "local order: {:#?}", result);
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self, order))]
+    async fn readd_local_order(&self, order: &LocalOrder) -> Result<LocalOrderResult> {
+        let result = self
+            .client()
+            .post::<_, LocalOrderResult>(Self::READD_LOCAL_ORDER, &self.token().await?, order)
+            .await?;
+
+        event!(Level::DEBUG, "local order: {:#?}", result);
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self, cancel))]
+    async fn pre_cancel_local_order(&self, cancel: &LocalCancel) -> Result<LocalCancelResult> {
+        self.client()
+            .post(Self::PRE_CANCEL_LOCAL_ORDER, &self.token().await?, cancel)
+            .await
+    }
+
+    #[instrument(skip(self, cancel))]
+    async fn cancel_local_order(&self, cancel: &LocalCancel) -> Result<LocalCancelResult> {
+        self.client()
+            .post(Self::CANCEL_LOCAL_ORDER, &self.token().await?, cancel)
+            .await
+    }
+
+    #[instrument(skip(self, key))]
+    async fn get_local_order(&self, key: &LocalOrderKey) -> Result<LocalOrderDetail> {
+        self.client()
+            .post(Self::GET_LOCAL_ORDER, &self.token().await?, key)
+            .await
+    }
+
+    #[instrument(skip(self, tips))]
+    async fn add_local_tips(&self, tips: &LocalTips) -> Result<LocalResult> {
+        self.client()
+            .post(Self::ADD_LOCAL_TIPS, &self.token().await?, tips)
+            .await
+    }
+
+    #[instrument(skip(self, key))]
+    async fn confirm_return(
+        &self,
+        key: &LocalOrderKey,
+        waybill_id: &str,
+        remark: &str,
+    ) -> Result<LocalResult> {
+        let request = ConfirmReturnRequest {
+            key,
+            waybill_id,
+            remark,
+        };
+
+        self.client()
+            .post(Self::CONFIRM_RETURN, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self, update))]
+    async fn mock_update_order(&self, update: &MockUpdateOrder) -> Result<LocalResult> {
+        self.client()
+            .post(Self::MOCK_UPDATE_ORDER, &self.token().await?, update)
+            .await
+    }
+
+    #[instrument(skip(self, update))]
+    async fn real_mock_update_order(&self, update: &MockUpdateOrder) -> Result<LocalResult> {
+        self.client()
+            .post(Self::REAL_MOCK_UPDATE_ORDER, &self.token().await?, update)
+            .await
+    }
+}
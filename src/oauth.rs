@@ -0,0 +1,366 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use tracing::{event, instrument, Level};
+use url::Url;
+
+use crate::{
+    client::Client,
+    id::{OpenId, UnionId},
+    secret::Secret,
+    transport::HttpRequest,
+    Result,
+};
+
+/// 公众号网页授权的作用域
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scope {
+    /// 静默授权，只能获取用户的 openid
+    #[default]
+    #[serde(rename = "snsapi_base")]
+    Base,
+    /// 弹出授权页面，可获取用户的昵称、头像等信息
+    #[serde(rename = "snsapi_userinfo")]
+    UserInfo,
+}
+
+impl Scope {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Base => "snsapi_base",
+            Self::UserInfo => "snsapi_userinfo",
+        }
+    }
+}
+
+/// 公众号网页授权或网站应用扫码登录的凭证，与小程序的 [`Credential`](crate::credential::Credential) 相对应
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OAuthCredential {
+    access_token: Secret,
+    expired_at: DateTime<Utc>,
+    refresh_token: Secret,
+    open_id: OpenId,
+    scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    union_id: Option<UnionId>,
+    is_snapshot_user: bool,
+}
+
+impl OAuthCredential {
+    /// 网页授权接口调用凭证，与基础支持中的 access_token 不同
+    pub fn access_token(&self) -> &str {
+        self.access_token.expose()
+    }
+
+    pub fn expired_at(&self) -> DateTime<Utc> {
+        self.expired_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expired_at <= Utc::now()
+    }
+
+    /// 用于刷新 access_token，有效期为 30 天
+    pub fn refresh_token(&self) -> &str {
+        self.refresh_token.expose()
+    }
+
+    pub fn open_id(&self) -> &OpenId {
+        &self.open_id
+    }
+
+    /// 用户授权的作用域，多个作用域以逗号分隔
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    pub fn union_id(&self) -> Option<&UnionId> {
+        self.union_id.as_ref()
+    }
+
+    /// 是否为快照页模式虚拟账号，此时 openid 不是用户的真实 openid
+    pub fn is_snapshot_user(&self) -> bool {
+        self.is_snapshot_user
+    }
+}
+
+impl std::fmt::Debug for OAuthCredential {
+    // 为了安全，不打印 access_token 和 refresh_token
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuthCredential")
+            .field("access_token", &"********")
+            .field("expired_at", &self.expired_at)
+            .field("refresh_token", &"********")
+            .field("open_id", &self.open_id)
+            .field("scope", &self.scope)
+            .field("union_id", &self.union_id)
+            .field("is_snapshot_user", &self.is_snapshot_user)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct OAuthCredentialBuilder {
+    access_token: Secret,
+    #[serde(
+        deserialize_with = "OAuthCredentialBuilder::deserialize_expired_at",
+        rename = "expires_in"
+    )]
+    expired_at: DateTime<Utc>,
+    refresh_token: Secret,
+    #[serde(rename = "openid")]
+    open_id: OpenId,
+    scope: String,
+    #[serde(rename = "unionid", default)]
+    union_id: Option<UnionId>,
+    #[serde(rename = "is_snapshotuser", default)]
+    is_snapshot_user: u8,
+}
+
+impl OAuthCredentialBuilder {
+    fn deserialize_expired_at<'de, D>(
+        deserializer: D,
+    ) -> std::result::Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let seconds = Duration::seconds(i64::deserialize(deserializer)?);
+
+        Ok(Utc::now() + seconds)
+    }
+
+    fn build(self) -> OAuthCredential {
+        OAuthCredential {
+            access_token: self.access_token,
+            expired_at: self.expired_at,
+            refresh_token: self.refresh_token,
+            open_id: self.open_id,
+            scope: self.scope,
+            union_id: self.union_id,
+            is_snapshot_user: self.is_snapshot_user == 1,
+        }
+    }
+}
+
+/// 通过网页授权获取的用户信息，需要 [`Scope::UserInfo`] 或网站应用的 `snsapi_login` 作用域
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OAuthUser {
+    #[serde(rename(deserialize = "openid"))]
+    open_id: OpenId,
+    #[serde(default)]
+    nickname: String,
+    #[serde(default)]
+    sex: u8,
+    #[serde(default)]
+    province: String,
+    #[serde(default)]
+    city: String,
+    #[serde(default)]
+    country: String,
+    #[serde(rename(deserialize = "headimgurl"), default)]
+    avatar: String,
+    #[serde(default)]
+    privilege: Vec<String>,
+    #[serde(rename(deserialize = "unionid"), default)]
+    union_id: Option<UnionId>,
+}
+
+impl OAuthUser {
+    pub fn open_id(&self) -> &OpenId {
+        &self.open_id
+    }
+
+    pub fn nickname(&self) -> &str {
+        &self.nickname
+    }
+
+    /// 性别，1 为男性，2 为女性，0 为未知
+    pub fn sex(&self) -> u8 {
+        self.sex
+    }
+
+    pub fn province(&self) -> &str {
+        &self.province
+    }
+
+    pub fn city(&self) -> &str {
+        &self.city
+    }
+
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+
+    /// 头像链接，用户没有头像时为空
+    pub fn avatar(&self) -> &str {
+        &self.avatar
+    }
+
+    /// 用户特权信息，如微信沃卡用户为 `chinaunicom`
+    pub fn privilege(&self) -> &[String] {
+        &self.privilege
+    }
+
+    pub fn union_id(&self) -> Option<&UnionId> {
+        self.union_id.as_ref()
+    }
+}
+
+#[derive(Serialize)]
+struct OpenIdQuery<'a> {
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+}
+
+impl Client {
+    const AUTHORIZE: &'static str = "https://open.weixin.qq.com/connect/oauth2/authorize";
+    const QRCONNECT: &'static str = "https://open.weixin.qq.com/connect/qrconnect";
+    const OAUTH_ACCESS_TOKEN: &'static str = "https://api.weixin.qq.com/sns/oauth2/access_token";
+    const OAUTH_REFRESH_TOKEN: &'static str = "https://api.weixin.qq.com/sns/oauth2/refresh_token";
+    const OAUTH_USER_INFO: &'static str = "https://api.weixin.qq.com/sns/userinfo";
+    const OAUTH_CHECK: &'static str = "https://api.weixin.qq.com/sns/auth";
+
+    /// 生成公众号网页授权链接，`Client` 需使用公众号的 appid 和 secret
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
+    /// ```rust
+    /// use axum::{extract::State, response::Redirect};
+    /// use open_wechat::{client::Client, oauth::Scope};
+    ///
+    /// pub(crate) async fn authorize(State(client): State<Client>) -> Redirect {
+    ///     let url = client.authorize_url("https://example.com/callback", Scope::UserInfo, "state");
+    ///
+    ///     Redirect::temporary(&url)
+    /// }
+    /// ```
+    pub fn authorize_url(&self, redirect_uri: &str, scope: Scope, state: &str) -> String {
+        self.oauth_url(Self::AUTHORIZE, redirect_uri, scope.as_str(), state)
+    }
+
+    /// 生成开放平台网站应用微信扫码登录链接，作用域为 `snsapi_login`，`Client` 需使用网站应用的 appid 和 secret，
+    /// 用户扫码确认后使用 [`Client::oauth_login`] 换取包含 unionid 的 [`OAuthCredential`]
+    /// https://developers.weixin.qq.com/doc/oplatform/Website_App/WeChat_Login/Wechat_Login.html
+    /// ```rust
+    /// use axum::{extract::State, response::Redirect};
+    /// use open_wechat::client::Client;
+    ///
+    /// pub(crate) async fn login(State(client): State<Client>) -> Redirect {
+    ///     let url = client.qrconnect_url("https://example.com/callback", "state");
+    ///
+    ///     Redirect::temporary(&url)
+    /// }
+    /// ```
+    pub fn qrconnect_url(&self, redirect_uri: &str, state: &str) -> String {
+        self.oauth_url(Self::QRCONNECT, redirect_uri, "snsapi_login", state)
+    }
+
+    fn oauth_url(&self, base: &str, redirect_uri: &str, scope: &str, state: &str) -> String {
+        let mut url = Url::parse_with_params(
+            base,
+            &[
+                ("appid", self.app_id()),
+                ("redirect_uri", redirect_uri),
+                ("response_type", "code"),
+                ("scope", scope),
+                ("state", state),
+            ],
+        )
+        .unwrap();
+
+        url.set_fragment(Some("wechat_redirect"));
+
+        url.into()
+    }
+
+    /// 通过网页授权或网站应用扫码登录的 code 换取网页授权 access_token
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
+    /// ```rust
+    /// use axum::{
+    ///     extract::{Query, State},
+    ///     response::IntoResponse,
+    ///     Json,
+    /// };
+    /// use open_wechat::{client::Client, Result};
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// pub(crate) struct Callback {
+    ///     code: String,
+    /// }
+    ///
+    /// pub(crate) async fn callback(
+    ///     State(client): State<Client>,
+    ///     Query(callback): Query<Callback>,
+    /// ) -> Result<impl IntoResponse> {
+    ///     let credential = client.oauth_login(&callback.code).await?;
+    ///
+    ///     let user = client.oauth_user_info(&credential).await?;
+    ///
+    ///     Ok(Json(json!({
+    ///         "open_id": credential.open_id(),
+    ///         "nickname": user.nickname(),
+    ///     })))
+    /// }
+    /// ```
+    #[instrument(skip(self, code))]
+    pub async fn oauth_login(&self, code: &str) -> Result<OAuthCredential> {
+        let query = [
+            ("appid", self.app_id()),
+            ("secret", self.secret()),
+            ("code", code),
+            ("grant_type", "authorization_code"),
+        ];
+
+        self.oauth_token(Self::OAUTH_ACCESS_TOKEN, &query).await
+    }
+
+    /// 刷新网页授权 access_token
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
+    #[instrument(skip(self, refresh_token))]
+    pub async fn refresh_oauth(&self, refresh_token: &str) -> Result<OAuthCredential> {
+        let query = [
+            ("appid", self.app_id()),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ];
+
+        self.oauth_token(Self::OAUTH_REFRESH_TOKEN, &query).await
+    }
+
+    async fn oauth_token(&self, url: &str, query: &[(&str, &str)]) -> Result<OAuthCredential> {
+        let request = HttpRequest::get(url)?.query(query)?;
+
+        let credential = self
+            .send(request)
+            .await?
+            .extract::<OAuthCredentialBuilder>()?
+            .build();
+
+        event!(Level::DEBUG, "oauth credential: {:#?}", credential);
+
+        Ok(credential)
+    }
+
+    /// 拉取用户信息，需要 [`Scope::UserInfo`] 或网站应用的 `snsapi_login` 作用域
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
+    #[instrument(skip(self, credential))]
+    pub async fn oauth_user_info(&self, credential: &OAuthCredential) -> Result<OAuthUser> {
+        let query = OpenIdQuery {
+            open_id: credential.open_id.as_str(),
+        };
+
+        self.get(Self::OAUTH_USER_INFO, &credential.access_token, &query)
+            .await
+    }
+
+    /// 检验网页授权 access_token 是否有效
+    /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
+    #[instrument(skip(self, credential))]
+    pub async fn check_oauth(&self, credential: &OAuthCredential) -> Result<()> {
+        let query = OpenIdQuery {
+            open_id: credential.open_id.as_str(),
+        };
+
+        self.get(Self::OAUTH_CHECK, &credential.access_token, &query)
+            .await
+    }
+}
@@ -0,0 +1,429 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{event, instrument, Level};
+
+use crate::{
+    credential::TokenProvider,
+    img::{Image, ImageSize, Position},
+    Result,
+};
+
+/// 身份证的正反面
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum IdCardSide {
+    /// 人像面
+    Front,
+    /// 国徽面
+    Back,
+}
+
+/// 身份证识别的结果，人像面返回姓名、身份证号等信息，国徽面只返回有效期
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IdCard {
+    #[serde(rename(deserialize = "type"))]
+    side: IdCardSide,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename(deserialize = "id"), default)]
+    id_number: Option<String>,
+    #[serde(rename(deserialize = "addr"), default)]
+    address: Option<String>,
+    #[serde(default)]
+    gender: Option<String>,
+    #[serde(default)]
+    nationality: Option<String>,
+    #[serde(default)]
+    valid_date: Option<String>,
+}
+
+impl IdCard {
+    pub fn side(&self) -> IdCardSide {
+        self.side
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// 身份证号
+    pub fn id_number(&self) -> Option<&str> {
+        self.id_number.as_deref()
+    }
+
+    pub fn address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+
+    pub fn gender(&self) -> Option<&str> {
+        self.gender.as_deref()
+    }
+
+    /// 民族
+    pub fn nationality(&self) -> Option<&str> {
+        self.nationality.as_deref()
+    }
+
+    /// 有效期，如 `20070105-20270105`
+    pub fn valid_date(&self) -> Option<&str> {
+        self.valid_date.as_deref()
+    }
+}
+
+impl std::fmt::Debug for IdCard {
+    // 为了安全，不打印身份证号和住址
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdCard")
+            .field("side", &self.side)
+            .field("name", &self.name)
+            .field("id_number", &"********")
+            .field("address", &"********")
+            .field("gender", &self.gender)
+            .field("nationality", &self.nationality)
+            .field("valid_date", &self.valid_date)
+            .finish()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NumberBuilder {
+    number: String,
+}
+
+/// 驾驶证识别的结果
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DrivingLicense {
+    #[serde(default)]
+    id_num: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    sex: String,
+    #[serde(default)]
+    nationality: String,
+    #[serde(default)]
+    address: String,
+    #[serde(default)]
+    birth_date: String,
+    #[serde(default)]
+    issue_date: String,
+    #[serde(default)]
+    car_class: String,
+    #[serde(default)]
+    valid_from: String,
+    #[serde(default)]
+    valid_to: String,
+    #[serde(default)]
+    official_seal: String,
+}
+
+impl DrivingLicense {
+    /// 证号
+    pub fn id_num(&self) -> &str {
+        &self.id_num
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn sex(&self) -> &str {
+        &self.sex
+    }
+
+    /// 国籍
+    pub fn nationality(&self) -> &str {
+        &self.nationality
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn birth_date(&self) -> &str {
+        &self.birth_date
+    }
+
+    /// 初次领证日期
+    pub fn issue_date(&self) -> &str {
+        &self.issue_date
+    }
+
+    /// 准驾车型
+    pub fn car_class(&self) -> &str {
+        &self.car_class
+    }
+
+    /// 有效期限起始日
+    pub fn valid_from(&self) -> &str {
+        &self.valid_from
+    }
+
+    /// 有效期限终止日
+    pub fn valid_to(&self) -> &str {
+        &self.valid_to
+    }
+
+    /// 印章文字
+    pub fn official_seal(&self) -> &str {
+        &self.official_seal
+    }
+}
+
+impl std::fmt::Debug for DrivingLicense {
+    // 为了安全，不打印证号、姓名和住址
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DrivingLicense")
+            .field("id_num", &"********")
+            .field("name", &"********")
+            .field("sex", &self.sex)
+            .field("nationality", &self.nationality)
+            .field("address", &"********")
+            .field("birth_date", &self.birth_date)
+            .field("issue_date", &self.issue_date)
+            .field("car_class", &self.car_class)
+            .field("valid_from", &self.valid_from)
+            .field("valid_to", &self.valid_to)
+            .field("official_seal", &self.official_seal)
+            .finish()
+    }
+}
+
+/// 营业执照识别的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BusinessLicense {
+    #[serde(default)]
+    reg_num: String,
+    #[serde(default)]
+    serial: String,
+    #[serde(default)]
+    legal_representative: String,
+    #[serde(default)]
+    enterprise_name: String,
+    #[serde(default)]
+    type_of_organization: String,
+    #[serde(default)]
+    address: String,
+    #[serde(default)]
+    type_of_enterprise: String,
+    #[serde(default)]
+    business_scope: String,
+    #[serde(default)]
+    registered_capital: String,
+    #[serde(default)]
+    paid_in_capital: String,
+    #[serde(default)]
+    valid_period: String,
+    #[serde(default)]
+    registered_date: String,
+}
+
+impl BusinessLicense {
+    /// 注册号或统一社会信用代码
+    pub fn reg_num(&self) -> &str {
+        &self.reg_num
+    }
+
+    /// 编号
+    pub fn serial(&self) -> &str {
+        &self.serial
+    }
+
+    /// 法定代表人姓名
+    pub fn legal_representative(&self) -> &str {
+        &self.legal_representative
+    }
+
+    /// 企业名称
+    pub fn enterprise_name(&self) -> &str {
+        &self.enterprise_name
+    }
+
+    /// 组成形式
+    pub fn type_of_organization(&self) -> &str {
+        &self.type_of_organization
+    }
+
+    /// 经营场所或企业住所
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// 公司类型
+    pub fn type_of_enterprise(&self) -> &str {
+        &self.type_of_enterprise
+    }
+
+    /// 经营范围
+    pub fn business_scope(&self) -> &str {
+        &self.business_scope
+    }
+
+    /// 注册资本
+    pub fn registered_capital(&self) -> &str {
+        &self.registered_capital
+    }
+
+    /// 实收资本
+    pub fn paid_in_capital(&self) -> &str {
+        &self.paid_in_capital
+    }
+
+    /// 营业期限
+    pub fn valid_period(&self) -> &str {
+        &self.valid_period
+    }
+
+    /// 注册日期或成立日期
+    pub fn registered_date(&self) -> &str {
+        &self.registered_date
+    }
+}
+
+/// 通用印刷体识别的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrintedText {
+    #[serde(default)]
+    items: Vec<TextItem>,
+    img_size: ImageSize,
+}
+
+impl PrintedText {
+    pub fn items(&self) -> &[TextItem] {
+        &self.items
+    }
+
+    pub fn img_size(&self) -> ImageSize {
+        self.img_size
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TextItem {
+    text: String,
+    pos: Position,
+}
+
+impl TextItem {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn pos(&self) -> Position {
+        self.pos
+    }
+}
+
+/// OCR 识别接口
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{
+///     credential::GenericAccessToken,
+///     img::Image,
+///     ocr::{IdCardSide, RecognizeImage},
+///     Result,
+/// };
+/// use serde_json::json;
+///
+/// pub(crate) async fn id_card(
+///     State(access_token): State<GenericAccessToken>,
+///     body: axum::body::Bytes,
+/// ) -> Result<impl IntoResponse> {
+///     let id_card = access_token
+///         .recognize_id_card(&Image::from(body.to_vec()))
+///         .await?;
+///
+///     Ok(Json(json!({
+///         "front": id_card.side() == IdCardSide::Front,
+///         "name": id_card.name(),
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait RecognizeImage {
+    const ID_CARD: &'static str = "https://api.weixin.qq.com/cv/ocr/idcard";
+    const BANK_CARD: &'static str = "https://api.weixin.qq.com/cv/ocr/bankcard";
+    const DRIVING_LICENSE: &'static str = "https://api.weixin.qq.com/cv/ocr/drivinglicense";
+    const BUSINESS_LICENSE: &'static str = "https://api.weixin.qq.com/cv/ocr/bizlicense";
+    const PRINTED_TEXT: &'static str = "https://api.weixin.qq.com/cv/ocr/comm";
+    const PLATE_NUMBER: &'static str = "https://api.weixin.qq.com/cv/ocr/platenum";
+
+    /// 身份证识别
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/ocr/idCardOCR.html
+    async fn recognize_id_card(&self, image: &Image) -> Result<IdCard>;
+
+    /// 银行卡识别，返回银行卡号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/ocr/bankCardOCR.html
+    async fn recognize_bank_card(&self, image: &Image) -> Result<String>;
+
+    /// 驾驶证识别
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/ocr/driverLicenseOCR.html
+    async fn recognize_driving_license(&self, image: &Image) -> Result<DrivingLicense>;
+
+    /// 营业执照识别
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/ocr/businessLicenseOCR.html
+    async fn recognize_business_license(&self, image: &Image) -> Result<BusinessLicense>;
+
+    /// 通用印刷体识别
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/ocr/printedTextOCR.html
+    async fn recognize_printed_text(&self, image: &Image) -> Result<PrintedText>;
+
+    /// 车牌识别，返回车牌号
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/img-ocr/ocr/plateNumberOCR.html
+    async fn recognize_plate_number(&self, image: &Image) -> Result<String>;
+}
+
+#[async_trait]
+impl<T> RecognizeImage for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, image))]
+    async fn recognize_id_card(&self, image: &Image) -> Result<IdCard> {
+        let id_card = image
+            .post::<IdCard>(self.client(), Self::ID_CARD, &self.token().await?)
+            .await?;
+
+        event!(Level::DEBUG, "id card: {:#?}", id_card);
+
+        Ok(id_card)
+    }
+
+    #[instrument(skip(self, image))]
+    async fn recognize_bank_card(&self, image: &Image) -> Result<String> {
+        let builder = image
+            .post::<NumberBuilder>(self.client(), Self::BANK_CARD, &self.token().await?)
+            .await?;
+
+        Ok(builder.number)
+    }
+
+    #[instrument(skip(self, image))]
+    async fn recognize_driving_license(&self, image: &Image) -> Result<DrivingLicense> {
+        image
+            .post(self.client(), Self::DRIVING_LICENSE, &self.token().await?)
+            .await
+    }
+
+    #[instrument(skip(self, image))]
+    async fn recognize_business_license(&self, image: &Image) -> Result<BusinessLicense> {
+        image
+            .post(self.client(), Self::BUSINESS_LICENSE, &self.token().await?)
+            .await
+    }
+
+    #[instrument(skip(self, image))]
+    async fn recognize_printed_text(&self, image: &Image) -> Result<PrintedText> {
+        image
+            .post(self.client(), Self::PRINTED_TEXT, &self.token().await?)
+            .await
+    }
+
+    #[instrument(skip(self, image))]
+    async fn recognize_plate_number(&self, image: &Image) -> Result<String> {
+        let builder = image
+            .post::<NumberBuilder>(self.client(), Self::PLATE_NUMBER, &self.token().await?)
+            .await?;
+
+        Ok(builder.number)
+    }
+}
@@ -0,0 +1,863 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{from_str, Value};
+use serde_repr::Serialize_repr;
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, id::OpenId, Result};
+
+/// 微信部分字段时而返回字符串、时而返回数字，统一转换为字符串
+fn deserialize_string<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(value) => Ok(value),
+        Value::Number(value) => Ok(value.to_string()),
+        Value::Null => Ok(String::new()),
+        value => Err(D::Error::custom(format!(
+            "expected string, found {}",
+            value
+        ))),
+    }
+}
+
+/// 微信部分字段时而返回字符串、时而返回数字，统一转换为数字
+fn deserialize_number<'de, D>(deserializer: D) -> std::result::Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Number(value) => value
+            .as_u64()
+            .ok_or_else(|| D::Error::custom(format!("expected u64, found {}", value))),
+        Value::String(value) => value.parse().map_err(D::Error::custom),
+        Value::Null => Ok(0),
+        value => Err(D::Error::custom(format!(
+            "expected number, found {}",
+            value
+        ))),
+    }
+}
+
+/// 实时日志查询参数，`begin_time` 和 `end_time` 必须在 `date` 当天
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct RealtimeLogQuery {
+    #[serde(serialize_with = "RealtimeLogQuery::serialize_date")]
+    pub date: NaiveDate,
+    #[serde(rename = "begintime", with = "chrono::serde::ts_seconds")]
+    pub begin_time: DateTime<Utc>,
+    #[serde(rename = "endtime", with = "chrono::serde::ts_seconds")]
+    pub end_time: DateTime<Utc>,
+    /// 开始返回的数据下标，默认为 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u32>,
+    /// 返回的数据条数，默认为 20，最大为 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// 小程序启动的唯一 ID，按 TraceId 查询会展示该次小程序启动过程的所有页面的日志
+    #[serde(rename = "traceId", skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// 小程序页面路径，例如 pages/index/index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 用户微信号或者 openid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// 开发者通过 setFilterMsg/addFilterMsg 指定的 filterMsg 字段
+    #[serde(rename = "filterMsg", skip_serializing_if = "Option::is_none")]
+    pub filter_msg: Option<String>,
+    /// 日志等级，返回大于等于 level 等级的日志，2 为 Info，4 为 Warn，8 为 Error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+}
+
+impl RealtimeLogQuery {
+    fn serialize_date<S>(date: &NaiveDate, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&date.format("%Y%m%d"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeLogBuilder {
+    data: RealtimeLogList,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RealtimeLogList {
+    #[serde(default)]
+    list: Vec<RealtimeLog>,
+    #[serde(default)]
+    total: u64,
+}
+
+impl RealtimeLogList {
+    pub fn list(&self) -> &[RealtimeLog] {
+        &self.list
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// 一次页面访问的实时日志
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct RealtimeLog {
+    level: u8,
+    platform: u8,
+    library_version: String,
+    client_version: String,
+    #[serde(deserialize_with = "deserialize_string")]
+    id: String,
+    timestamp: i64,
+    #[serde(default)]
+    msg: Vec<RealtimeLogMessage>,
+    url: String,
+}
+
+impl RealtimeLog {
+    /// 日志等级，是 msg 数组里面的所有 level 字段的或操作得到的结果
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// 用户终端平台
+    pub fn platform(&self) -> u8 {
+        self.platform
+    }
+
+    /// 基础库版本
+    pub fn library_version(&self) -> &str {
+        &self.library_version
+    }
+
+    /// 客户端版本
+    pub fn client_version(&self) -> &str {
+        &self.client_version
+    }
+
+    /// 用户微信号或者 openid
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    pub fn msg(&self) -> &[RealtimeLogMessage] {
+        &self.msg
+    }
+
+    /// 小程序页面链接
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RealtimeLogMessage {
+    time: i64,
+    #[serde(default)]
+    msg: Vec<String>,
+    level: u8,
+}
+
+impl RealtimeLogMessage {
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// 开发者调用 wx.getRealtimeLogManager 写入的日志内容
+    pub fn msg(&self) -> &[String] {
+        &self.msg
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+}
+
+/// 错误查询列表参数，日期格式为 `2021-08-01`
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsErrQuery {
+    /// 小程序版本，"0" 表示全部版本
+    pub app_version: String,
+    /// 错误类型，"0" 全部，"1" 业务代码错误，"2" 插件错误，"3" 系统框架错误
+    pub err_type: String,
+    pub start_time: NaiveDate,
+    pub end_time: NaiveDate,
+    /// 从错误中搜索的关键词，关键词过滤
+    pub keyword: String,
+    /// 发生错误的用户 openid，为 `None` 时不按用户过滤
+    #[serde(rename = "openid", serialize_with = "OpenId::serialize_optional")]
+    pub open_id: Option<OpenId>,
+    /// 排序字段 uv, pv
+    pub orderby: String,
+    /// 排序规则，"1" 降序，"2" 升序
+    pub desc: String,
+    pub offset: u32,
+    /// 取条数，最大为 30
+    pub limit: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JsErrList {
+    #[serde(rename(deserialize = "data"), default)]
+    list: Vec<JsErr>,
+    #[serde(rename(deserialize = "totalCount"), default)]
+    total_count: u64,
+}
+
+impl JsErrList {
+    pub fn list(&self) -> &[JsErr] {
+        &self.list
+    }
+
+    pub fn total_count(&self) -> u64 {
+        self.total_count
+    }
+}
+
+/// 按错误信息聚合的 JS 错误
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct JsErr {
+    error_msg_md5: String,
+    error_msg: String,
+    #[serde(deserialize_with = "deserialize_number")]
+    uv: u64,
+    #[serde(deserialize_with = "deserialize_number")]
+    pv: u64,
+    error_stack_md5: String,
+    error_stack: String,
+    #[serde(default, deserialize_with = "deserialize_string")]
+    pv_percent: String,
+    #[serde(default, deserialize_with = "deserialize_string")]
+    uv_percent: String,
+}
+
+impl JsErr {
+    /// 错误信息的 md5，用于查询错误详情
+    pub fn error_msg_md5(&self) -> &str {
+        &self.error_msg_md5
+    }
+
+    pub fn error_msg(&self) -> &str {
+        &self.error_msg
+    }
+
+    /// 影响用户数
+    pub fn uv(&self) -> u64 {
+        self.uv
+    }
+
+    /// 错误次数
+    pub fn pv(&self) -> u64 {
+        self.pv
+    }
+
+    /// 错误堆栈的 md5，用于查询错误详情
+    pub fn error_stack_md5(&self) -> &str {
+        &self.error_stack_md5
+    }
+
+    pub fn error_stack(&self) -> &str {
+        &self.error_stack
+    }
+
+    /// 错误次数占比
+    pub fn pv_percent(&self) -> &str {
+        &self.pv_percent
+    }
+
+    /// 影响用户数占比
+    pub fn uv_percent(&self) -> &str {
+        &self.uv_percent
+    }
+}
+
+/// 错误详情查询参数，日期格式为 `2021-08-01`
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsErrDetailQuery {
+    pub start_time: NaiveDate,
+    pub end_time: NaiveDate,
+    /// 错误列表查询接口返回的 errorMsgMd5
+    pub error_msg_md5: String,
+    /// 错误列表查询接口返回的 errorStackMd5
+    pub error_stack_md5: String,
+    /// 小程序版本，"0" 表示全部版本
+    pub app_version: String,
+    /// 基础库版本，"0" 表示全部版本
+    pub sdk_version: String,
+    /// 系统类型，"0" 全部，"1" 安卓，"2" IOS，"3" 其他
+    pub os_name: String,
+    /// 客户端版本，"0" 表示全部版本
+    pub client_version: String,
+    /// 发生错误的用户 openid，为 `None` 时不按用户过滤
+    #[serde(rename = "openid", serialize_with = "OpenId::serialize_optional")]
+    pub open_id: Option<OpenId>,
+    pub offset: u32,
+    /// 取条数，最大为 30
+    pub limit: u32,
+    /// 排序规则，"1" 降序，"2" 升序
+    pub desc: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JsErrDetailList {
+    #[serde(rename(deserialize = "data"), default)]
+    list: Vec<JsErrDetail>,
+    #[serde(default)]
+    count: u64,
+}
+
+impl JsErrDetailList {
+    pub fn list(&self) -> &[JsErrDetail] {
+        &self.list
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// 单条 JS 错误详情，字段名与微信接口返回的大小写不一致
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JsErrDetail {
+    #[serde(rename(deserialize = "Count"), deserialize_with = "deserialize_number")]
+    count: u64,
+    #[serde(rename(deserialize = "sdkVersion"), default)]
+    sdk_version: String,
+    #[serde(rename(deserialize = "ClientVersion"), default)]
+    client_version: String,
+    #[serde(rename(deserialize = "errorStackMd5"), default)]
+    error_stack_md5: String,
+    #[serde(rename(deserialize = "TimeStamp"), default)]
+    timestamp: String,
+    #[serde(rename(deserialize = "appVersion"), default)]
+    app_version: String,
+    #[serde(rename(deserialize = "errorMsgMd5"), default)]
+    error_msg_md5: String,
+    #[serde(rename(deserialize = "errorMsg"), default)]
+    error_msg: String,
+    #[serde(rename(deserialize = "errorStack"), default)]
+    error_stack: String,
+    #[serde(rename(deserialize = "OsName"), default)]
+    os_name: String,
+    #[serde(
+        rename(deserialize = "openId"),
+        default,
+        deserialize_with = "OpenId::deserialize_optional"
+    )]
+    open_id: Option<OpenId>,
+    #[serde(rename(deserialize = "DeviceModel"), default)]
+    device_model: String,
+    #[serde(default)]
+    route: String,
+}
+
+impl JsErrDetail {
+    /// 错误次数
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 基础库版本
+    pub fn sdk_version(&self) -> &str {
+        &self.sdk_version
+    }
+
+    /// 客户端版本
+    pub fn client_version(&self) -> &str {
+        &self.client_version
+    }
+
+    pub fn error_stack_md5(&self) -> &str {
+        &self.error_stack_md5
+    }
+
+    /// 发生错误的时间，如 `2021-08-03 17:23:38`
+    pub fn timestamp(&self) -> &str {
+        &self.timestamp
+    }
+
+    /// 小程序版本
+    pub fn app_version(&self) -> &str {
+        &self.app_version
+    }
+
+    pub fn error_msg_md5(&self) -> &str {
+        &self.error_msg_md5
+    }
+
+    pub fn error_msg(&self) -> &str {
+        &self.error_msg
+    }
+
+    pub fn error_stack(&self) -> &str {
+        &self.error_stack
+    }
+
+    /// 系统类型
+    pub fn os_name(&self) -> &str {
+        &self.os_name
+    }
+
+    pub fn open_id(&self) -> Option<&OpenId> {
+        self.open_id.as_ref()
+    }
+
+    /// 机型
+    pub fn device_model(&self) -> &str {
+        &self.device_model
+    }
+
+    /// 发生错误的页面
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+}
+
+/// 性能监控的耗时类型
+#[derive(Debug, Serialize_repr, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum CostTimeType {
+    /// 启动总耗时
+    #[default]
+    Launch = 1,
+    /// 下载耗时
+    Download = 2,
+    /// 初次渲染耗时
+    FirstRender = 3,
+}
+
+/// 性能监控查询参数，过滤条件默认为 `@_all` 表示全部
+#[derive(Debug, Serialize, Clone)]
+pub struct CostTimeQuery {
+    pub cost_time_type: CostTimeType,
+    #[serde(rename = "default_start_time", with = "chrono::serde::ts_seconds")]
+    pub start_time: DateTime<Utc>,
+    #[serde(rename = "default_end_time", with = "chrono::serde::ts_seconds")]
+    pub end_time: DateTime<Utc>,
+    /// 机型
+    pub device: String,
+    /// 是否下载代码包，"1" 是，"2" 否
+    pub is_download_code: String,
+    /// 访问来源
+    pub scene: String,
+    /// 网络环境，如 `wifi`、`4g`
+    pub networktype: String,
+}
+
+impl Default for CostTimeQuery {
+    fn default() -> Self {
+        Self {
+            cost_time_type: CostTimeType::default(),
+            start_time: DateTime::default(),
+            end_time: DateTime::default(),
+            device: "@_all".into(),
+            is_download_code: "@_all".into(),
+            scene: "@_all".into(),
+            networktype: "@_all".into(),
+        }
+    }
+}
+
+// 两个字段都是 JSON 字符串，需要再次解析
+#[derive(Debug, Deserialize)]
+struct CostTimeBuilder {
+    default_time_data: String,
+    #[serde(default)]
+    compare_time_data: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct CostTimeData {
+    #[serde(default)]
+    list: Vec<CostTime>,
+}
+
+impl CostTimeData {
+    fn parse(data: &str) -> Result<Vec<CostTime>> {
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(from_str::<CostTimeData>(data)?.list)
+    }
+}
+
+/// 性能监控数据
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CostTimeTrend {
+    default_time_data: Vec<CostTime>,
+    compare_time_data: Vec<CostTime>,
+}
+
+impl CostTimeTrend {
+    /// 查询时间段内的数据
+    pub fn default_time_data(&self) -> &[CostTime] {
+        &self.default_time_data
+    }
+
+    /// 对比时间段内的数据
+    pub fn compare_time_data(&self) -> &[CostTime] {
+        &self.compare_time_data
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CostTime {
+    #[serde(deserialize_with = "deserialize_string")]
+    ref_date: String,
+    cost_time_type: u8,
+    #[serde(deserialize_with = "deserialize_number")]
+    cost_time: u64,
+}
+
+impl CostTime {
+    /// 日期，如 `20220303`
+    pub fn ref_date(&self) -> &str {
+        &self.ref_date
+    }
+
+    pub fn cost_time_type(&self) -> u8 {
+        self.cost_time_type
+    }
+
+    /// 耗时，单位为毫秒
+    pub fn cost_time(&self) -> u64 {
+        self.cost_time
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SceneBuilder {
+    #[serde(default)]
+    scene: Vec<Scene>,
+}
+
+/// 访问来源
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Scene {
+    name: String,
+    #[serde(deserialize_with = "deserialize_string")]
+    value: String,
+}
+
+impl Scene {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 场景值，用于性能监控的 `scene` 查询条件
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientVersionBuilder {
+    #[serde(rename = "cvlist", default)]
+    list: Vec<ClientVersion>,
+}
+
+/// 客户端版本
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClientVersion {
+    #[serde(rename(deserialize = "type"))]
+    version_type: u8,
+    #[serde(default)]
+    client_version_list: Vec<String>,
+}
+
+impl ClientVersion {
+    /// 查询类型，1 为 android，2 为 iOS
+    pub fn version_type(&self) -> u8 {
+        self.version_type
+    }
+
+    pub fn client_version_list(&self) -> &[String] {
+        &self.client_version_list
+    }
+}
+
+/// 用户反馈查询参数
+#[derive(Debug, Serialize, Clone)]
+pub struct FeedbackQuery {
+    /// 反馈类型，1 无法打开小程序，2 小程序闪退，3 卡顿，4 黑屏白屏，5 死机，6 界面错位，
+    /// 7 界面加载慢，8 其他异常，不填时返回全部类型
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub feedback_type: Option<u8>,
+    /// 分页的页数，从 1 开始
+    pub page: u32,
+    /// 分页拉取的数据数量
+    pub num: u32,
+}
+
+impl Default for FeedbackQuery {
+    fn default() -> Self {
+        Self {
+            feedback_type: None,
+            page: 1,
+            num: 10,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedbackList {
+    #[serde(default)]
+    list: Vec<Feedback>,
+    #[serde(default)]
+    total_num: u64,
+}
+
+impl FeedbackList {
+    pub fn list(&self) -> &[Feedback] {
+        &self.list
+    }
+
+    pub fn total_num(&self) -> u64 {
+        self.total_num
+    }
+}
+
+/// 用户反馈
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Feedback {
+    record_id: u64,
+    create_time: i64,
+    content: String,
+    #[serde(default, deserialize_with = "deserialize_string")]
+    phone: String,
+    #[serde(rename(deserialize = "openid"))]
+    open_id: OpenId,
+    #[serde(default)]
+    nickname: String,
+    #[serde(default)]
+    head_url: String,
+    #[serde(rename(deserialize = "type"))]
+    feedback_type: u8,
+    #[serde(rename(deserialize = "mediaIds"), default)]
+    media_ids: Vec<String>,
+    #[serde(rename(deserialize = "systemInfo"), default)]
+    system_info: String,
+}
+
+impl Feedback {
+    pub fn record_id(&self) -> u64 {
+        self.record_id
+    }
+
+    pub fn create_time(&self) -> i64 {
+        self.create_time
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn phone(&self) -> &str {
+        &self.phone
+    }
+
+    pub fn open_id(&self) -> &OpenId {
+        &self.open_id
+    }
+
+    pub fn nickname(&self) -> &str {
+        &self.nickname
+    }
+
+    pub fn head_url(&self) -> &str {
+        &self.head_url
+    }
+
+    pub fn feedback_type(&self) -> u8 {
+        self.feedback_type
+    }
+
+    /// 反馈图片的 media_id，用于下载反馈图片
+    pub fn media_ids(&self) -> &[String] {
+        &self.media_ids
+    }
+
+    /// 用户的设备和网络信息，JSON 字符串
+    pub fn system_info(&self) -> &str {
+        &self.system_info
+    }
+}
+
+#[derive(Serialize)]
+struct FeedbackMediaQuery<'a> {
+    record_id: u64,
+    media_id: &'a str,
+}
+
+/// 运维中心接口，查询实时日志、JS 错误、性能监控和用户反馈
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{
+///     credential::GenericAccessToken,
+///     operation::{JsErrQuery, ManageOperation},
+///     Result,
+/// };
+/// use serde_json::json;
+///
+/// pub(crate) async fn js_errors(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let query = JsErrQuery {
+///         app_version: "0".into(),
+///         err_type: "0".into(),
+///         orderby: "uv".into(),
+///         desc: "1".into(),
+///         limit: 10,
+///         ..Default::default()
+///     };
+///
+///     let errors = access_token.get_js_err_list(&query).await?;
+///
+///     Ok(Json(json!({
+///         "errors": errors,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait ManageOperation {
+    const REALTIME_LOG_SEARCH: &'static str =
+        "https://api.weixin.qq.com/wxaapi/userlog/userlog_search";
+    const JS_ERR_LIST: &'static str = "https://api.weixin.qq.com/wxaapi/log/jserr_list";
+    const JS_ERR_DETAIL: &'static str = "https://api.weixin.qq.com/wxaapi/log/jserr_detail";
+    const PERFORMANCE: &'static str = "https://api.weixin.qq.com/wxaapi/log/get_performance";
+    const SCENE_LIST: &'static str = "https://api.weixin.qq.com/wxaapi/log/get_scene";
+    const VERSION_LIST: &'static str = "https://api.weixin.qq.com/wxaapi/log/get_client_version";
+    const FEEDBACK: &'static str = "https://api.weixin.qq.com/wxaapi/feedback/list";
+    const FEEDBACK_MEDIA: &'static str = "https://api.weixin.qq.com/cgi-bin/media/getfeedbackmedia";
+
+    /// 查询实时日志，只能查询最近 14 天的日志
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/realtimelogSearch.html
+    async fn search_realtime_log(&self, query: &RealtimeLogQuery) -> Result<RealtimeLogList>;
+
+    /// 查询错误列表
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/getJsErrList.html
+    async fn get_js_err_list(&self, query: &JsErrQuery) -> Result<JsErrList>;
+
+    /// 查询错误详情
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/getJsErrDetail.html
+    async fn get_js_err_detail(&self, query: &JsErrDetailQuery) -> Result<JsErrDetailList>;
+
+    /// 获取性能监控数据
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/getPerformance.html
+    async fn get_performance(&self, query: &CostTimeQuery) -> Result<CostTimeTrend>;
+
+    /// 获取访问来源，用于性能监控的 `scene` 查询条件
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/getSceneList.html
+    async fn get_scene_list(&self) -> Result<Vec<Scene>>;
+
+    /// 获取客户端版本
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/getVersionList.html
+    async fn get_version_list(&self) -> Result<Vec<ClientVersion>>;
+
+    /// 获取用户反馈列表
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/getFeedback.html
+    async fn get_feedback(&self, query: &FeedbackQuery) -> Result<FeedbackList>;
+
+    /// 下载用户反馈的图片
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/operation/getFeedbackmedia.html
+    async fn get_feedback_media(&self, record_id: u64, media_id: &str) -> Result<Vec<u8>>;
+}
+
+#[async_trait]
+impl<T> ManageOperation for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, query))]
+    async fn search_realtime_log(&self, query: &RealtimeLogQuery) -> Result<RealtimeLogList> {
+        let builder = self
+            .client()
+            .get::<_, RealtimeLogBuilder>(Self::REALTIME_LOG_SEARCH, &self.token().await?, query)
+            .await?;
+
+        event!(Level::DEBUG, "realtime log total: {}", builder.data.total);
+
+        Ok(builder.data)
+    }
+
+    #[instrument(skip(self, query))]
+    async fn get_js_err_list(&self, query: &JsErrQuery) -> Result<JsErrList> {
+        self.client()
+            .post(Self::JS_ERR_LIST, &self.token().await?, query)
+            .await
+    }
+
+    #[instrument(skip(self, query))]
+    async fn get_js_err_detail(&self, query: &JsErrDetailQuery) -> Result<JsErrDetailList> {
+        self.client()
+            .post(Self::JS_ERR_DETAIL, &self.token().await?, query)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_performance(&self, query: &CostTimeQuery) -> Result<CostTimeTrend> {
+        let builder = self
+            .client()
+            .post::<_, CostTimeBuilder>(Self::PERFORMANCE, &self.token().await?, query)
+            .await?;
+
+        Ok(CostTimeTrend {
+            default_time_data: CostTimeData::parse(&builder.default_time_data)?,
+            compare_time_data: CostTimeData::parse(&builder.compare_time_data)?,
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_scene_list(&self) -> Result<Vec<Scene>> {
+        let builder = self
+            .client()
+            .get::<_, SceneBuilder>(Self::SCENE_LIST, &self.token().await?, &())
+            .await?;
+
+        Ok(builder.scene)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_version_list(&self) -> Result<Vec<ClientVersion>> {
+        let builder = self
+            .client()
+            .get::<_, ClientVersionBuilder>(Self::VERSION_LIST, &self.token().await?, &())
+            .await?;
+
+        Ok(builder.list)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_feedback(&self, query: &FeedbackQuery) -> Result<FeedbackList> {
+        self.client()
+            .get(Self::FEEDBACK, &self.token().await?, query)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_feedback_media(&self, record_id: u64, media_id: &str) -> Result<Vec<u8>> {
+        let query = FeedbackMediaQuery {
+            record_id,
+            media_id,
+        };
+
+        self.client()
+            .download(Self::FEEDBACK_MEDIA, &self.token().await?, &query)
+            .await
+    }
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// 以分为单位的金额，微信支付所有金额字段均以分为单位
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(transparent)]
+pub struct Fen(u64);
+
+impl Fen {
+    pub const fn new(fen: u64) -> Self {
+        Self(fen)
+    }
+
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for Fen {
+    fn from(fen: u64) -> Self {
+        Self(fen)
+    }
+}
+
+impl From<Fen> for u64 {
+    fn from(fen: Fen) -> Self {
+        fen.0
+    }
+}
+
+impl std::fmt::Display for Fen {
+    // 以元为单位显示，如 `12.34`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{:02}", self.0 / 100, self.0 % 100)
+    }
+}
@@ -0,0 +1,146 @@
+use chrono::NaiveDate;
+use hex::encode;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tracing::{event, instrument, Level};
+
+use crate::{error::Error::PayVerification, Result};
+
+use super::PayClient;
+
+/// 申请交易账单参数
+#[derive(Debug, Serialize, Clone)]
+pub struct TradeBillRequest {
+    /// 账单日期，仅支持三个月内的账单
+    pub bill_date: NaiveDate,
+    /// 账单类型，不填则默认为 `ALL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bill_type: Option<BillType>,
+    /// 压缩类型，不填则默认为数据流
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tar_type: Option<TarType>,
+}
+
+/// 申请资金账单参数
+#[derive(Debug, Serialize, Clone)]
+pub struct FundFlowBillRequest {
+    /// 账单日期，仅支持三个月内的账单
+    pub bill_date: NaiveDate,
+    /// 资金账户类型，不填则默认为 `BASIC`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<AccountType>,
+    /// 压缩类型，不填则默认为数据流
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tar_type: Option<TarType>,
+}
+
+/// 账单类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum BillType {
+    /// 当日所有订单信息（不含充值退款订单）
+    All,
+    /// 当日成功支付的订单（不含充值退款订单）
+    Success,
+    /// 当日退款订单（不含充值退款订单）
+    Refund,
+}
+
+/// 资金账户类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum AccountType {
+    /// 基本账户
+    Basic,
+    /// 运营账户
+    Operation,
+    /// 手续费账户
+    Fees,
+}
+
+/// 压缩类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TarType {
+    Gzip,
+}
+
+/// 账单下载地址及摘要，下载地址 30 秒内有效
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Bill {
+    hash_type: String,
+    hash_value: String,
+    download_url: String,
+}
+
+impl Bill {
+    /// 哈希类型，固定为 `SHA1`
+    pub fn hash_type(&self) -> &str {
+        &self.hash_type
+    }
+
+    pub fn hash_value(&self) -> &str {
+        &self.hash_value
+    }
+
+    pub fn download_url(&self) -> &str {
+        &self.download_url
+    }
+}
+
+impl PayClient {
+    const TRADE_BILL: &'static str = "/v3/bill/tradebill";
+    const FUND_FLOW_BILL: &'static str = "/v3/bill/fundflowbill";
+
+    /// 申请交易账单
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/trade-bill.html
+    #[instrument(skip(self))]
+    pub async fn trade_bill(&self, request: &TradeBillRequest) -> Result<Bill> {
+        let path = format!(
+            "{}?{}",
+            Self::TRADE_BILL,
+            serde_urlencoded::to_string(request)?
+        );
+
+        let bill = self.get::<Bill>(&path).await?;
+
+        event!(Level::DEBUG, "bill: {:#?}", bill);
+
+        Ok(bill)
+    }
+
+    /// 申请资金账单
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/fund-bill.html
+    #[instrument(skip(self))]
+    pub async fn fund_flow_bill(&self, request: &FundFlowBillRequest) -> Result<Bill> {
+        let path = format!(
+            "{}?{}",
+            Self::FUND_FLOW_BILL,
+            serde_urlencoded::to_string(request)?
+        );
+
+        let bill = self.get::<Bill>(&path).await?;
+
+        event!(Level::DEBUG, "bill: {:#?}", bill);
+
+        Ok(bill)
+    }
+
+    /// 下载账单，并校验账单文件的 SHA1 摘要
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/download-bill.html
+    #[instrument(skip(self, bill))]
+    pub async fn download_bill(&self, bill: &Bill) -> Result<Vec<u8>> {
+        let content = self.download(&bill.download_url).await?;
+
+        let digest = encode(Sha1::digest(&content));
+
+        if !digest.eq_ignore_ascii_case(&bill.hash_value) {
+            return Err(PayVerification(format!(
+                "bill digest mismatch, expected {}, got {}",
+                bill.hash_value, digest
+            )));
+        }
+
+        Ok(content)
+    }
+}
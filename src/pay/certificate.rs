@@ -0,0 +1,227 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use http::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::from_slice;
+use tokio::sync::RwLock;
+use tracing::{event, instrument, Level};
+
+use crate::{refresh::RefreshOnce, Result};
+
+use super::{verify::Signed, PayClient};
+
+/// 微信支付平台证书
+#[derive(Debug, Serialize, Clone)]
+pub struct PlatformCertificate {
+    serial_no: String,
+    effective_time: DateTime<FixedOffset>,
+    expire_time: DateTime<FixedOffset>,
+    certificate: String,
+}
+
+impl PlatformCertificate {
+    pub fn serial_no(&self) -> &str {
+        &self.serial_no
+    }
+
+    pub fn effective_time(&self) -> DateTime<FixedOffset> {
+        self.effective_time
+    }
+
+    pub fn expire_time(&self) -> DateTime<FixedOffset> {
+        self.expire_time
+    }
+
+    /// PEM 格式的证书
+    pub fn certificate(&self) -> &str {
+        &self.certificate
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CertificatesBuilder {
+    data: Vec<PlatformCertificateBuilder>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlatformCertificateBuilder {
+    serial_no: String,
+    effective_time: DateTime<FixedOffset>,
+    expire_time: DateTime<FixedOffset>,
+    encrypt_certificate: EncryptCertificate,
+}
+
+#[derive(Debug, Deserialize)]
+struct EncryptCertificate {
+    nonce: String,
+    #[serde(default)]
+    associated_data: String,
+    ciphertext: String,
+}
+
+/// 平台证书的自动更新状态
+#[derive(Debug, Default)]
+pub(crate) struct Rotation {
+    enabled: AtomicBool,
+    refresh: RefreshOnce,
+    refreshed_at: RwLock<Option<DateTime<Utc>>>,
+}
+
+impl Rotation {
+    /// 微信支付建议每 12 小时更新一次平台证书
+    const INTERVAL: i64 = 12 * 60 * 60;
+
+    /// 遇到未知序列号时，距上次更新至少间隔 60 秒才会再次更新，避免被伪造的序列号频繁触发下载
+    const COOLDOWN: i64 = 60;
+}
+
+impl PayClient {
+    const CERTIFICATES: &'static str = "/v3/certificates";
+
+    /// 下载微信支付平台证书，并使用 APIv3 密钥解密，解密后的证书会被缓存用于验签
+    /// https://pay.weixin.qq.com/docs/merchant/apis/platform-certificate/api-v3-get-certificates/get.html
+    #[instrument(skip(self))]
+    pub async fn download_certificates(&self) -> Result<Vec<PlatformCertificate>> {
+        let response = self
+            .send(Method::GET, Self::CERTIFICATES, String::new())
+            .await?;
+
+        let headers = response.headers;
+        let body = response.body;
+
+        let builder = from_slice::<CertificatesBuilder>(&body)?;
+
+        let mut certificates = Vec::with_capacity(builder.data.len());
+
+        for certificate in builder.data {
+            let plaintext = self.cipher().decrypt(
+                &certificate.encrypt_certificate.ciphertext,
+                &certificate.encrypt_certificate.nonce,
+                &certificate.encrypt_certificate.associated_data,
+            )?;
+
+            certificates.push(PlatformCertificate {
+                serial_no: certificate.serial_no,
+                effective_time: certificate.effective_time,
+                expire_time: certificate.expire_time,
+                certificate: String::from_utf8_lossy(&plaintext).into_owned(),
+            });
+        }
+
+        let signed = Signed::from_headers(&headers)?;
+
+        // 下载平台证书的应答可能使用其中的平台证书签名，验签通过后才缓存证书
+        self.verifier()
+            .add_signed_certificates(
+                &certificates
+                    .iter()
+                    .map(|certificate| certificate.certificate.as_str())
+                    .collect::<Vec<_>>(),
+                &signed,
+                &body,
+            )
+            .await?;
+
+        event!(
+            Level::DEBUG,
+            "platform certificates: {:?}",
+            certificates
+                .iter()
+                .map(PlatformCertificate::serial_no)
+                .collect::<Vec<_>>()
+        );
+
+        Ok(certificates)
+    }
+
+    /// 下载并缓存微信支付平台证书，移除已过期的平台证书，调用后每 12 小时自动更新一次，
+    /// 遇到未知的证书序列号时也会自动更新。未调用且未添加微信支付公钥时，首次验签前会自动调用
+    /// ```ignore
+    /// use open_wechat::{client::Client, pay::PayClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your app id", "your app secret");
+    ///
+    ///     let private_key = std::fs::read_to_string("apiclient_key.pem")?;
+    ///
+    ///     let pay_client = PayClient::new(
+    ///         client,
+    ///         "your mch id",
+    ///         "your serial no",
+    ///         &private_key,
+    ///         "your api v3 key",
+    ///     )?;
+    ///
+    ///     pay_client.refresh_certificates().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn refresh_certificates(&self) -> Result<()> {
+        let rotation = self.rotation();
+
+        self.download_certificates().await?;
+
+        self.verifier().remove_expired().await;
+
+        *rotation.refreshed_at.write().await = Some(Utc::now());
+
+        rotation.enabled.store(true, Ordering::Release);
+
+        event!(Level::DEBUG, "platform certificates refreshed");
+
+        Ok(())
+    }
+
+    /// 验证应答或回调通知的签名，必要时先更新平台证书
+    pub(crate) async fn verify(&self, signed: &Signed<'_>, body: &[u8]) -> Result<()> {
+        self.rotate(signed.serial).await?;
+
+        self.verifier().verify(signed, body).await
+    }
+
+    /// 未配置平台证书或微信支付公钥、平台证书过期或遇到未知序列号时更新平台证书，
+    /// 同一时间只有一个更新任务，其他任务等待更新完成
+    async fn rotate(&self, serial: &str) -> Result<()> {
+        self.rotation()
+            .refresh
+            .get(|| self.rotated(serial), || self.refresh_certificates())
+            .await
+    }
+
+    /// 无需更新平台证书时返回 `Some`
+    async fn rotated(&self, serial: &str) -> Option<()> {
+        let rotation = self.rotation();
+
+        // 没有任何证书或公钥时必须先下载平台证书，不能跳过验签
+        let empty = self.verifier().is_empty().await;
+
+        if !empty && !rotation.enabled.load(Ordering::Acquire) {
+            return Some(());
+        }
+
+        let refreshed_at = rotation.refreshed_at.read().await.unwrap_or_default();
+        let elapsed = Utc::now() - refreshed_at;
+
+        let expired = elapsed >= Duration::seconds(Rotation::INTERVAL);
+        let unknown = elapsed >= Duration::seconds(Rotation::COOLDOWN)
+            && !self.verifier().contains(serial).await;
+
+        if !empty && !expired && !unknown {
+            return Some(());
+        }
+
+        event!(
+            Level::DEBUG,
+            "empty: {}, expired: {}, unknown serial: {}",
+            empty,
+            expired,
+            unknown
+        );
+
+        None
+    }
+}
@@ -0,0 +1,55 @@
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, KeyInit, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::Result;
+
+/// 使用 APIv3 密钥进行 AEAD_AES_256_GCM 解密
+pub(crate) struct Cipher {
+    inner: Aes256Gcm,
+}
+
+impl Cipher {
+    /// APIv3 密钥长度必须为 32 字节
+    pub(crate) fn new(api_v3_key: &str) -> Result<Self> {
+        Ok(Self {
+            inner: Aes256Gcm::new_from_slice(api_v3_key.as_bytes())?,
+        })
+    }
+
+    /// 解密回调通知或平台证书中的密文，`ciphertext` 为 Base64 编码
+    /// https://pay.weixin.qq.com/docs/merchant/development/interface-rules/certificate-callback-decryption.html
+    pub(crate) fn decrypt(
+        &self,
+        ciphertext: &str,
+        nonce: &str,
+        associated_data: &str,
+    ) -> Result<Vec<u8>> {
+        // 随机串长度固定为 12 字节，否则 `Nonce::from_slice` 会 panic
+        if nonce.len() != 12 {
+            return Err(aes_gcm::Error.into());
+        }
+
+        let ciphertext = STANDARD.decode(ciphertext.as_bytes())?;
+
+        let payload = Payload {
+            msg: &ciphertext,
+            aad: associated_data.as_bytes(),
+        };
+
+        let plaintext = self
+            .inner
+            .decrypt(Nonce::from_slice(nonce.as_bytes()), payload)?;
+
+        Ok(plaintext)
+    }
+}
+
+impl std::fmt::Debug for Cipher {
+    // 为了安全，不打印 APIv3 密钥
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cipher").field("key", &"********").finish()
+    }
+}
@@ -0,0 +1,234 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize, Serializer};
+use tracing::{event, instrument, Level};
+
+use crate::{id::OpenId, Result};
+
+use super::{amount::Fen, sign, PayClient};
+
+/// JSAPI/小程序下单参数
+/// ```rust
+/// use chrono::{FixedOffset, TimeZone};
+/// use open_wechat::pay::jsapi::JsapiOrder;
+///
+/// let time_expire = FixedOffset::east_opt(8 * 3600)
+///     .unwrap()
+///     .with_ymd_and_hms(2026, 10, 19, 3, 40, 31)
+///     .unwrap()
+///     + chrono::Duration::nanoseconds(40_256_380);
+///
+/// let order = JsapiOrder {
+///     time_expire: Some(time_expire),
+///     ..Default::default()
+/// };
+///
+/// let value = serde_json::to_value(&order).unwrap();
+///
+/// assert_eq!(value["time_expire"], "2026-10-19T03:40:31+08:00");
+/// ```
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct JsapiOrder {
+    /// 商品描述
+    pub description: String,
+    /// 商户系统内部订单号，只能是数字、大小写字母_-*且在同一个商户号下唯一
+    pub out_trade_no: String,
+    /// 支付结束时间，精确到秒
+    #[serde(
+        serialize_with = "JsapiOrder::serialize_time_expire",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub time_expire: Option<DateTime<FixedOffset>>,
+    /// 商户数据包，在查询订单和支付通知中原样返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attach: Option<String>,
+    /// 商户接收支付成功回调通知的地址，需为外网可访问的 https 地址
+    pub notify_url: String,
+    /// 订单优惠标记
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goods_tag: Option<String>,
+    /// 是否支持电子发票
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub support_fapiao: Option<bool>,
+    pub amount: OrderAmount,
+}
+
+impl JsapiOrder {
+    // 微信支付只接受 `yyyy-MM-DDTHH:mm:ss+TIMEZONE`，不能带小数秒
+    fn serialize_time_expire<S>(
+        time_expire: &Option<DateTime<FixedOffset>>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match time_expire {
+            Some(time_expire) => {
+                serializer.collect_str(&time_expire.format("%Y-%m-%dT%H:%M:%S%:z"))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// 订单金额
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct OrderAmount {
+    /// 订单总金额
+    pub total: Fen,
+    /// 货币类型，境内商户号仅支持人民币 `CNY`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsapiOrderRequest<'a> {
+    #[serde(rename = "appid")]
+    app_id: &'a str,
+    #[serde(rename = "mchid")]
+    mch_id: &'a str,
+    #[serde(flatten)]
+    order: &'a JsapiOrder,
+    payer: Payer<'a>,
+}
+
+#[derive(Serialize)]
+struct Payer<'a> {
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+}
+
+/// 预支付交易会话
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Prepay {
+    prepay_id: String,
+}
+
+impl Prepay {
+    pub fn prepay_id(&self) -> &str {
+        &self.prepay_id
+    }
+}
+
+/// 小程序调起支付 `wx.requestPayment` 所需的参数
+/// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/mini-transfer-payment.html
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPayment {
+    time_stamp: String,
+    nonce_str: String,
+    package: String,
+    sign_type: String,
+    pay_sign: String,
+}
+
+impl RequestPayment {
+    pub fn time_stamp(&self) -> &str {
+        &self.time_stamp
+    }
+
+    pub fn nonce_str(&self) -> &str {
+        &self.nonce_str
+    }
+
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    pub fn sign_type(&self) -> &str {
+        &self.sign_type
+    }
+
+    pub fn pay_sign(&self) -> &str {
+        &self.pay_sign
+    }
+}
+
+impl PayClient {
+    const JSAPI: &'static str = "/v3/pay/transactions/jsapi";
+
+    /// JSAPI/小程序下单，`open_id` 为用户在小程序下的唯一标识，即 [`Credential::open_id`](crate::credential::Credential::open_id)
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/mini-prepay.html
+    /// ```rust
+    /// use axum::{extract::State, response::IntoResponse, Json};
+    /// use open_wechat::{
+    ///     client::Client,
+    ///     id::LoginCode,
+    ///     pay::{
+    ///         jsapi::{JsapiOrder, OrderAmount},
+    ///         PayClient,
+    ///     },
+    ///     Result,
+    /// };
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// pub(crate) struct Purchaser {
+    ///     code: LoginCode,
+    ///     total: u64,
+    /// }
+    ///
+    /// pub(crate) async fn purchase(
+    ///     State((client, pay_client)): State<(Client, PayClient)>,
+    ///     Json(purchaser): Json<Purchaser>,
+    /// ) -> Result<impl IntoResponse> {
+    ///     let credential = client.login(&purchaser.code).await?;
+    ///
+    ///     let order = JsapiOrder {
+    ///         description: "your description".into(),
+    ///         out_trade_no: "your out trade no".into(),
+    ///         notify_url: "https://example.com/notify".into(),
+    ///         amount: OrderAmount {
+    ///             total: purchaser.total.into(),
+    ///             ..Default::default()
+    ///         },
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let prepay = pay_client
+    ///         .create_jsapi_order(credential.open_id(), &order)
+    ///         .await?;
+    ///
+    ///     Ok(Json(pay_client.request_payment(prepay.prepay_id())))
+    /// }
+    /// ```
+    #[instrument(skip(self, order))]
+    pub async fn create_jsapi_order(&self, open_id: &OpenId, order: &JsapiOrder) -> Result<Prepay> {
+        let request = JsapiOrderRequest {
+            app_id: self.app_id(),
+            mch_id: self.mch_id(),
+            order,
+            payer: Payer {
+                open_id: open_id.as_str(),
+            },
+        };
+
+        let prepay = self.post::<_, Prepay>(Self::JSAPI, &request).await?;
+
+        event!(Level::DEBUG, "prepay: {:#?}", prepay);
+
+        Ok(prepay)
+    }
+
+    /// 生成小程序调起支付的参数，签名串为 `appId\ntimeStamp\nnonceStr\npackage\n`
+    pub fn request_payment(&self, prepay_id: &str) -> RequestPayment {
+        let time_stamp = sign::timestamp().to_string();
+        let nonce_str = sign::nonce();
+        let package = format!("prepay_id={}", prepay_id);
+
+        let message = format!(
+            "{}\n{}\n{}\n{}\n",
+            self.app_id(),
+            time_stamp,
+            nonce_str,
+            package
+        );
+
+        RequestPayment {
+            pay_sign: self.signer().sign(&message),
+            time_stamp,
+            nonce_str,
+            package,
+            sign_type: "RSA".into(),
+        }
+    }
+}
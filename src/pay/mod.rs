@@ -0,0 +1,284 @@
+//! 微信支付 APIv3
+//!
+//! 需要开启 `pay` feature。
+
+pub mod amount;
+pub mod bill;
+pub mod certificate;
+mod cipher;
+pub mod jsapi;
+pub mod notify;
+pub mod refund;
+mod sign;
+pub mod transaction;
+mod verify;
+
+use std::sync::Arc;
+
+use http::{
+    header::{HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT},
+    Method,
+};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{from_slice, from_str};
+use tracing::{event, instrument, Level};
+
+use crate::{
+    client::Client,
+    error::{
+        Error::{InternalServer, Pay, PayVerification},
+        PayError,
+    },
+    transport::{HttpRequest, HttpResponse},
+    Result,
+};
+
+use self::{
+    certificate::Rotation,
+    cipher::Cipher,
+    sign::Signer,
+    verify::{Signed, Verifier},
+};
+
+/// 路径中除非保留字符以外的字符都需要编码
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// 编码调用方传入的路径参数，如 `out_trade_no`，避免其中的 `/`、`?` 等字符改变请求的路径
+pub(crate) fn path_segment(value: &str) -> String {
+    utf8_percent_encode(value, PATH_SEGMENT).to_string()
+}
+
+/// 存储微信支付商户号、商户 API 证书序列号、商户 API 证书私钥和 APIv3 密钥
+#[derive(Debug, Clone)]
+pub struct PayClient {
+    inner: Arc<PayClientInner>,
+}
+
+impl PayClient {
+    const DOMAIN: &'static str = "https://api.mch.weixin.qq.com";
+
+    const SCHEMA: &'static str = "WECHATPAY2-SHA256-RSA2048";
+
+    const USER_AGENT: &'static str = concat!("open-wechat/", env!("CARGO_PKG_VERSION"));
+
+    /// ```ignore
+    /// use open_wechat::{client::Client, pay::PayClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("your app id", "your app secret");
+    ///
+    ///     let private_key = std::fs::read_to_string("apiclient_key.pem")?;
+    ///
+    ///     let pay_client = PayClient::new(
+    ///         client,
+    ///         "your mch id",
+    ///         "your serial no",
+    ///         &private_key,
+    ///         "your api v3 key",
+    ///     )?;
+    ///
+    ///     let public_key = std::fs::read_to_string("pub_key.pem")?;
+    ///
+    ///     pay_client
+    ///         .add_public_key("your public key id", &public_key)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new(
+        client: Client,
+        mch_id: &str,
+        serial_no: &str,
+        private_key: &str,
+        api_v3_key: &str,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: Arc::new(PayClientInner {
+                client,
+                mch_id: mch_id.into(),
+                serial_no: serial_no.into(),
+                signer: Signer::new(private_key)?,
+                cipher: Cipher::new(api_v3_key)?,
+                verifier: Verifier::default(),
+                rotation: Rotation::default(),
+            }),
+        })
+    }
+
+    /// 添加微信支付公钥，用于验证应答和回调通知的签名，`public_key_id` 形如 `PUB_KEY_ID_...`
+    /// https://pay.weixin.qq.com/docs/merchant/development/interface-rules/wechatpay-pay-publickey.html
+    pub async fn add_public_key(&self, public_key_id: &str, public_key: &str) -> Result<()> {
+        self.inner
+            .verifier
+            .add_public_key(public_key_id, public_key)
+            .await
+    }
+
+    /// 添加微信支付平台证书，用于验证应答和回调通知的签名，返回证书序列号
+    /// https://pay.weixin.qq.com/docs/merchant/development/interface-rules/wechatpay-certificates.html
+    pub async fn add_certificate(&self, certificate: &str) -> Result<String> {
+        self.inner.verifier.add_certificate(certificate).await
+    }
+
+    pub fn mch_id(&self) -> &str {
+        &self.inner.mch_id
+    }
+
+    pub(crate) fn app_id(&self) -> &str {
+        self.inner.client.app_id()
+    }
+
+    pub(crate) fn signer(&self) -> &Signer {
+        &self.inner.signer
+    }
+
+    pub(crate) fn cipher(&self) -> &Cipher {
+        &self.inner.cipher
+    }
+
+    pub(crate) fn verifier(&self) -> &Verifier {
+        &self.inner.verifier
+    }
+
+    pub(crate) fn rotation(&self) -> &Rotation {
+        &self.inner.rotation
+    }
+
+    pub(crate) async fn get<T>(&self, path: &str) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let body = self.execute(Method::GET, path, String::new()).await?;
+
+        Ok(from_slice::<T>(&body)?)
+    }
+
+    pub(crate) async fn post<B, T>(&self, path: &str, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let body = serde_json::to_string(body)?;
+
+        let body = self.execute(Method::POST, path, body).await?;
+
+        Ok(from_slice::<T>(&body)?)
+    }
+
+    /// 应答为 `204 No Content` 的请求
+    pub(crate) async fn post_without_content<B>(&self, path: &str, body: &B) -> Result<()>
+    where
+        B: Serialize + ?Sized,
+    {
+        let body = serde_json::to_string(body)?;
+
+        self.execute(Method::POST, path, body).await?;
+
+        Ok(())
+    }
+
+    /// 下载文件，`url` 为接口返回的完整下载地址，只接受微信支付域名下的地址，下载文件的应答没有签名
+    pub(crate) async fn download(&self, url: &str) -> Result<Vec<u8>> {
+        let path = url
+            .strip_prefix(Self::DOMAIN)
+            .filter(|path| path.starts_with('/'))
+            .ok_or_else(|| PayVerification(format!("unexpected download url {}", url)))?;
+
+        let response = self.send(Method::GET, path, String::new()).await?;
+
+        Ok(response.body)
+    }
+
+    /// 发送请求并验证应答的签名，未配置平台证书或微信支付公钥时先下载平台证书
+    /// https://pay.weixin.qq.com/docs/merchant/development/interface-rules/signature-verification.html
+    async fn execute(&self, method: Method, path: &str, body: String) -> Result<Vec<u8>> {
+        let response = self.send(method, path, body).await?;
+
+        let signed = Signed::from_headers(&response.headers)?;
+
+        self.verify(&signed, &response.body).await?;
+
+        Ok(response.body)
+    }
+
+    /// 构造签名串并签名，发送请求
+    /// https://pay.weixin.qq.com/docs/merchant/development/interface-rules/signature-generation.html
+    #[instrument(skip(self, body))]
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: String,
+    ) -> Result<HttpResponse> {
+        let timestamp = sign::timestamp();
+        let nonce = sign::nonce();
+
+        let message = format!("{}\n{}\n{}\n{}\n{}\n", method, path, timestamp, nonce, body);
+
+        let authorization = format!(
+            r#"{} mchid="{}",nonce_str="{}",signature="{}",timestamp="{}",serial_no="{}""#,
+            Self::SCHEMA,
+            self.inner.mch_id,
+            nonce,
+            self.inner.signer.sign(&message),
+            timestamp,
+            self.inner.serial_no,
+        );
+
+        let mut request = HttpRequest::new(method, &format!("{}{}", Self::DOMAIN, path))?;
+
+        request.headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&authorization)
+                .map_err(|error| InternalServer(error.to_string()))?,
+        );
+        request
+            .headers
+            .insert(ACCEPT, HeaderValue::from_static("application/json"));
+        request
+            .headers
+            .insert(USER_AGENT, HeaderValue::from_static(Self::USER_AGENT));
+
+        if !body.is_empty() {
+            request = request.body("application/json", body.into_bytes());
+        }
+
+        let response = self.inner.client.send(request).await?;
+
+        if response.status.is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status;
+        let text = response.text();
+
+        match from_str::<PayError>(&text) {
+            Ok(error) => {
+                let error = error.with_status(status.as_u16());
+
+                event!(Level::ERROR, "微信支付返回错误: {}", error);
+
+                Err(Pay(error))
+            }
+            Err(_) => Err(InternalServer(text)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PayClientInner {
+    client: Client,
+    mch_id: String,
+    serial_no: String,
+    signer: Signer,
+    cipher: Cipher,
+    verifier: Verifier,
+    rotation: Rotation,
+}
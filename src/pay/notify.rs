@@ -0,0 +1,148 @@
+use chrono::{DateTime, FixedOffset};
+use http::header::HeaderMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{from_slice, from_str};
+use tracing::{event, instrument, Level};
+
+use crate::{
+    error::{Error::Pay, PayError},
+    Result,
+};
+
+use super::{verify::Signed, PayClient};
+
+/// 支付或退款回调通知，`resource` 为解密后的数据
+#[derive(Debug, Serialize, Clone)]
+pub struct Notification<T> {
+    id: String,
+    create_time: DateTime<FixedOffset>,
+    event_type: String,
+    resource_type: String,
+    summary: String,
+    resource: T,
+}
+
+impl<T> Notification<T> {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn create_time(&self) -> DateTime<FixedOffset> {
+        self.create_time
+    }
+
+    /// 通知类型，如 `TRANSACTION.SUCCESS`、`REFUND.SUCCESS`
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn resource_type(&self) -> &str {
+        &self.resource_type
+    }
+
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    pub fn resource(&self) -> &T {
+        &self.resource
+    }
+
+    pub fn into_resource(self) -> T {
+        self.resource
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NotificationBuilder {
+    id: String,
+    create_time: DateTime<FixedOffset>,
+    event_type: String,
+    resource_type: String,
+    summary: String,
+    resource: Resource,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resource {
+    algorithm: String,
+    ciphertext: String,
+    #[serde(default)]
+    associated_data: String,
+    nonce: String,
+}
+
+impl PayClient {
+    /// 回调通知数据的加密算法
+    const ALGORITHM: &'static str = "AEAD_AES_256_GCM";
+
+    /// 验证回调通知的签名，并使用 APIv3 密钥解密通知数据
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/payment-notice.html
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/refund-result-notice.html
+    /// ```rust
+    /// use axum::{extract::State, http::HeaderMap, response::IntoResponse};
+    /// use open_wechat::{
+    ///     pay::{transaction::Transaction, PayClient},
+    ///     Result,
+    /// };
+    ///
+    /// pub(crate) async fn notify(
+    ///     State(pay_client): State<PayClient>,
+    ///     headers: HeaderMap,
+    ///     body: String,
+    /// ) -> Result<impl IntoResponse> {
+    ///     let notification = pay_client
+    ///         .parse_notification::<Transaction>(&headers, &body)
+    ///         .await?;
+    ///
+    ///     let transaction = notification.into_resource();
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[instrument(skip(self, headers, body))]
+    pub async fn parse_notification<T>(
+        &self,
+        headers: &HeaderMap,
+        body: &str,
+    ) -> Result<Notification<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let signed = Signed::from_headers(headers)?;
+
+        self.verify(&signed, body.as_bytes()).await?;
+
+        let builder = from_str::<NotificationBuilder>(body)?;
+
+        event!(
+            Level::DEBUG,
+            "notification: id={}, event_type={}, algorithm={}",
+            builder.id,
+            builder.event_type,
+            builder.resource.algorithm
+        );
+
+        if builder.resource.algorithm != Self::ALGORITHM {
+            return Err(Pay(PayError::new(
+                "UNSUPPORTED_ALGORITHM",
+                format!("unsupported algorithm {}", builder.resource.algorithm),
+            )));
+        }
+
+        let plaintext = self.cipher().decrypt(
+            &builder.resource.ciphertext,
+            &builder.resource.nonce,
+            &builder.resource.associated_data,
+        )?;
+
+        Ok(Notification {
+            id: builder.id,
+            create_time: builder.create_time,
+            event_type: builder.event_type,
+            resource_type: builder.resource_type,
+            summary: builder.summary,
+            resource: from_slice::<T>(&plaintext)?,
+        })
+    }
+}
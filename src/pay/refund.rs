@@ -0,0 +1,235 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use tracing::{event, instrument, Level};
+
+use crate::Result;
+
+use super::{amount::Fen, path_segment, PayClient};
+
+/// 申请退款参数，`transaction_id` 和 `out_trade_no` 二选一
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct RefundOrder {
+    /// 微信支付订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    /// 商户订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_trade_no: Option<String>,
+    /// 商户系统内部的退款单号，同一退款单号多次请求只退一笔
+    pub out_refund_no: String,
+    /// 退款原因，会在下发给用户的退款消息中体现
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// 退款结果回调地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_url: Option<String>,
+    /// 退款资金来源，仅支持 `AVAILABLE`，即可用余额账户
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funds_account: Option<String>,
+    pub amount: RefundOrderAmount,
+}
+
+/// 申请退款金额
+#[derive(Debug, Serialize, Clone)]
+pub struct RefundOrderAmount {
+    /// 退款金额，不能超过原订单支付金额
+    pub refund: Fen,
+    /// 原订单金额
+    pub total: Fen,
+    /// 退款币种，目前只支持人民币 `CNY`
+    pub currency: String,
+}
+
+impl Default for RefundOrderAmount {
+    fn default() -> Self {
+        Self {
+            refund: Fen::default(),
+            total: Fen::default(),
+            currency: "CNY".into(),
+        }
+    }
+}
+
+/// 微信支付退款单
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Refund {
+    #[serde(rename(deserialize = "mchid"))]
+    mch_id: Option<String>,
+    transaction_id: String,
+    out_trade_no: String,
+    refund_id: String,
+    out_refund_no: String,
+    channel: Option<RefundChannel>,
+    #[serde(alias = "refund_status")]
+    status: RefundStatus,
+    success_time: Option<DateTime<FixedOffset>>,
+    create_time: Option<DateTime<FixedOffset>>,
+    user_received_account: Option<String>,
+    funds_account: Option<String>,
+    amount: RefundAmount,
+}
+
+impl Refund {
+    /// 退款回调通知中返回，查询退款时不返回
+    pub fn mch_id(&self) -> Option<&str> {
+        self.mch_id.as_deref()
+    }
+
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    pub fn out_trade_no(&self) -> &str {
+        &self.out_trade_no
+    }
+
+    pub fn refund_id(&self) -> &str {
+        &self.refund_id
+    }
+
+    pub fn out_refund_no(&self) -> &str {
+        &self.out_refund_no
+    }
+
+    /// 退款渠道，查询退款时返回
+    pub fn channel(&self) -> Option<RefundChannel> {
+        self.channel
+    }
+
+    pub fn status(&self) -> RefundStatus {
+        self.status
+    }
+
+    pub fn success_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.success_time
+    }
+
+    /// 退款受理时间，查询退款时返回
+    pub fn create_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.create_time
+    }
+
+    /// 退款入账账户
+    pub fn user_received_account(&self) -> Option<&str> {
+        self.user_received_account.as_deref()
+    }
+
+    /// 资金账户，查询退款时返回
+    pub fn funds_account(&self) -> Option<&str> {
+        self.funds_account.as_deref()
+    }
+
+    pub fn amount(&self) -> &RefundAmount {
+        &self.amount
+    }
+}
+
+/// 退款金额
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RefundAmount {
+    total: Fen,
+    refund: Fen,
+    payer_total: Fen,
+    payer_refund: Fen,
+    settlement_refund: Option<Fen>,
+    settlement_total: Option<Fen>,
+    discount_refund: Option<Fen>,
+    currency: Option<String>,
+}
+
+impl RefundAmount {
+    /// 原订单金额
+    pub fn total(&self) -> Fen {
+        self.total
+    }
+
+    /// 退款金额
+    pub fn refund(&self) -> Fen {
+        self.refund
+    }
+
+    /// 用户实际支付金额
+    pub fn payer_total(&self) -> Fen {
+        self.payer_total
+    }
+
+    /// 用户实际退款金额
+    pub fn payer_refund(&self) -> Fen {
+        self.payer_refund
+    }
+
+    /// 应结退款金额，去掉非充值代金券退款金额后的退款金额
+    pub fn settlement_refund(&self) -> Option<Fen> {
+        self.settlement_refund
+    }
+
+    /// 应结订单金额
+    pub fn settlement_total(&self) -> Option<Fen> {
+        self.settlement_total
+    }
+
+    /// 优惠退款金额
+    pub fn discount_refund(&self) -> Option<Fen> {
+        self.discount_refund
+    }
+
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_deref()
+    }
+}
+
+/// 退款渠道
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RefundChannel {
+    /// 原路退款
+    Original,
+    /// 退回到余额
+    Balance,
+    /// 原账户异常退到其他余额账户
+    OtherBalance,
+    /// 原银行卡异常退到其他银行卡
+    OtherBankcard,
+}
+
+/// 退款状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RefundStatus {
+    /// 退款成功
+    Success,
+    /// 退款关闭
+    Closed,
+    /// 退款处理中
+    Processing,
+    /// 退款异常
+    Abnormal,
+}
+
+impl PayClient {
+    const REFUNDS: &'static str = "/v3/refund/domestic/refunds";
+
+    /// 申请退款
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/create.html
+    #[instrument(skip(self, order))]
+    pub async fn create_refund(&self, order: &RefundOrder) -> Result<Refund> {
+        let refund = self.post::<_, Refund>(Self::REFUNDS, order).await?;
+
+        event!(Level::DEBUG, "refund: {:#?}", refund);
+
+        Ok(refund)
+    }
+
+    /// 查询单笔退款
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/query-by-out-refund-no.html
+    #[instrument(skip(self))]
+    pub async fn query_refund(&self, out_refund_no: &str) -> Result<Refund> {
+        let path = format!("{}/{}", Self::REFUNDS, path_segment(out_refund_no));
+
+        let refund = self.get::<Refund>(&path).await?;
+
+        event!(Level::DEBUG, "refund: {:#?}", refund);
+
+        Ok(refund)
+    }
+}
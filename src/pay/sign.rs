@@ -0,0 +1,56 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use rand::{distributions::Alphanumeric, Rng};
+use rsa::{
+    pkcs1v15::SigningKey,
+    pkcs8::DecodePrivateKey,
+    signature::{SignatureEncoding, Signer as _},
+    RsaPrivateKey,
+};
+use sha2::Sha256;
+
+use crate::Result;
+
+/// 使用商户 API 证书私钥进行 SHA256 with RSA 签名
+pub(crate) struct Signer {
+    key: SigningKey<Sha256>,
+}
+
+impl Signer {
+    /// 私钥为 PKCS#8 格式的 PEM 字符串，即商户 API 证书中的 `apiclient_key.pem`
+    pub(crate) fn new(private_key: &str) -> Result<Self> {
+        let key = RsaPrivateKey::from_pkcs8_pem(private_key)?;
+
+        Ok(Self {
+            key: SigningKey::new(key),
+        })
+    }
+
+    /// 对签名串进行签名，并对签名结果进行 Base64 编码
+    pub(crate) fn sign(&self, message: &str) -> String {
+        let signature = self.key.sign(message.as_bytes());
+
+        STANDARD.encode(signature.to_bytes())
+    }
+}
+
+impl std::fmt::Debug for Signer {
+    // 为了安全，不打印私钥
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer").field("key", &"********").finish()
+    }
+}
+
+/// 生成 32 位随机字符串
+pub(crate) fn nonce() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+/// 当前的 Unix 时间戳，单位为秒
+pub(crate) fn timestamp() -> i64 {
+    Utc::now().timestamp()
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use tracing::{event, instrument, Level};
+
+use crate::{id::OpenId, Result};
+
+use super::{amount::Fen, path_segment, PayClient};
+
+/// 微信支付订单
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Transaction {
+    #[serde(rename(deserialize = "appid"))]
+    app_id: String,
+    #[serde(rename(deserialize = "mchid"))]
+    mch_id: String,
+    out_trade_no: String,
+    transaction_id: Option<String>,
+    trade_type: Option<TradeType>,
+    trade_state: TradeState,
+    trade_state_desc: String,
+    bank_type: Option<String>,
+    attach: Option<String>,
+    success_time: Option<DateTime<FixedOffset>>,
+    payer: Option<TransactionPayer>,
+    amount: Option<TransactionAmount>,
+}
+
+impl Transaction {
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    pub fn mch_id(&self) -> &str {
+        &self.mch_id
+    }
+
+    pub fn out_trade_no(&self) -> &str {
+        &self.out_trade_no
+    }
+
+    /// 微信支付订单号，未支付时不返回
+    pub fn transaction_id(&self) -> Option<&str> {
+        self.transaction_id.as_deref()
+    }
+
+    pub fn trade_type(&self) -> Option<TradeType> {
+        self.trade_type
+    }
+
+    pub fn trade_state(&self) -> TradeState {
+        self.trade_state
+    }
+
+    pub fn trade_state_desc(&self) -> &str {
+        &self.trade_state_desc
+    }
+
+    pub fn bank_type(&self) -> Option<&str> {
+        self.bank_type.as_deref()
+    }
+
+    pub fn attach(&self) -> Option<&str> {
+        self.attach.as_deref()
+    }
+
+    pub fn success_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.success_time
+    }
+
+    pub fn open_id(&self) -> Option<&OpenId> {
+        self.payer.as_ref().map(|payer| &payer.open_id)
+    }
+
+    pub fn amount(&self) -> Option<&TransactionAmount> {
+        self.amount.as_ref()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct TransactionPayer {
+    #[serde(rename(deserialize = "openid"))]
+    open_id: OpenId,
+}
+
+/// 微信支付订单金额
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransactionAmount {
+    total: Fen,
+    payer_total: Option<Fen>,
+    currency: Option<String>,
+    payer_currency: Option<String>,
+}
+
+impl TransactionAmount {
+    /// 订单总金额
+    pub fn total(&self) -> Fen {
+        self.total
+    }
+
+    /// 用户实际支付金额
+    pub fn payer_total(&self) -> Option<Fen> {
+        self.payer_total
+    }
+
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_deref()
+    }
+
+    pub fn payer_currency(&self) -> Option<&str> {
+        self.payer_currency.as_deref()
+    }
+}
+
+/// 交易类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TradeType {
+    Jsapi,
+    Native,
+    App,
+    Micropay,
+    Mweb,
+    Facepay,
+}
+
+/// 交易状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TradeState {
+    /// 支付成功
+    Success,
+    /// 转入退款
+    Refund,
+    /// 未支付
+    NotPay,
+    /// 已关闭
+    Closed,
+    /// 已撤销（仅付款码支付会返回）
+    Revoked,
+    /// 用户支付中（仅付款码支付会返回）
+    UserPaying,
+    /// 支付失败（仅付款码支付会返回）
+    PayError,
+}
+
+#[derive(Serialize)]
+struct CloseRequest<'a> {
+    #[serde(rename = "mchid")]
+    mch_id: &'a str,
+}
+
+impl PayClient {
+    const TRANSACTION_BY_ID: &'static str = "/v3/pay/transactions/id";
+    const TRANSACTION_BY_OUT_TRADE_NO: &'static str = "/v3/pay/transactions/out-trade-no";
+
+    /// 微信支付订单号查询订单
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/query-by-wx-trade-no.html
+    #[instrument(skip(self))]
+    pub async fn query_transaction_by_id(&self, transaction_id: &str) -> Result<Transaction> {
+        let path = format!(
+            "{}/{}?mchid={}",
+            Self::TRANSACTION_BY_ID,
+            path_segment(transaction_id),
+            self.mch_id()
+        );
+
+        let transaction = self.get::<Transaction>(&path).await?;
+
+        event!(Level::DEBUG, "transaction: {:#?}", transaction);
+
+        Ok(transaction)
+    }
+
+    /// 商户订单号查询订单
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/query-by-out-trade-no.html
+    #[instrument(skip(self))]
+    pub async fn query_transaction_by_out_trade_no(
+        &self,
+        out_trade_no: &str,
+    ) -> Result<Transaction> {
+        let path = format!(
+            "{}/{}?mchid={}",
+            Self::TRANSACTION_BY_OUT_TRADE_NO,
+            path_segment(out_trade_no),
+            self.mch_id()
+        );
+
+        let transaction = self.get::<Transaction>(&path).await?;
+
+        event!(Level::DEBUG, "transaction: {:#?}", transaction);
+
+        Ok(transaction)
+    }
+
+    /// 关闭订单，订单生成后不能马上调用关单接口，最短调用时间间隔为 5 分钟
+    /// https://pay.weixin.qq.com/docs/merchant/apis/mini-program-payment/close-order.html
+    #[instrument(skip(self))]
+    pub async fn close_transaction(&self, out_trade_no: &str) -> Result<()> {
+        let path = format!(
+            "{}/{}/close",
+            Self::TRANSACTION_BY_OUT_TRADE_NO,
+            path_segment(out_trade_no)
+        );
+
+        let request = CloseRequest {
+            mch_id: self.mch_id(),
+        };
+
+        self.post_without_content(&path, &request).await
+    }
+}
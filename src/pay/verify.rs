@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use http::header::HeaderMap;
+use rsa::{
+    pkcs1v15::{Signature, VerifyingKey},
+    pkcs8::DecodePublicKey,
+    signature::Verifier as _,
+    RsaPublicKey,
+};
+use sha2::Sha256;
+use tokio::sync::RwLock;
+use tracing::{event, Level};
+use x509_cert::{
+    der::{DecodePem, Encode},
+    Certificate,
+};
+
+use crate::{error::Error::PayVerification, Result};
+
+use super::sign;
+
+/// 应答和回调通知中的签名信息
+#[derive(Debug)]
+pub(crate) struct Signed<'a> {
+    pub(crate) serial: &'a str,
+    pub(crate) signature: &'a str,
+    pub(crate) timestamp: &'a str,
+    pub(crate) nonce: &'a str,
+}
+
+impl<'a> Signed<'a> {
+    const SIGNATURE: &'static str = "Wechatpay-Signature";
+    const SERIAL: &'static str = "Wechatpay-Serial";
+    const TIMESTAMP: &'static str = "Wechatpay-Timestamp";
+    const NONCE: &'static str = "Wechatpay-Nonce";
+
+    pub(crate) fn from_headers(headers: &'a HeaderMap) -> Result<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| PayVerification(format!("missing header {}", name)))
+        };
+
+        Ok(Self {
+            serial: header(Self::SERIAL)?,
+            signature: header(Self::SIGNATURE)?,
+            timestamp: header(Self::TIMESTAMP)?,
+            nonce: header(Self::NONCE)?,
+        })
+    }
+}
+
+/// 微信支付平台证书或微信支付公钥，键为证书序列号或公钥 ID
+#[derive(Debug, Default)]
+pub(crate) struct Verifier {
+    keys: RwLock<HashMap<String, Key>>,
+}
+
+#[derive(Debug)]
+struct Key {
+    key: RsaPublicKey,
+    // 微信支付公钥没有有效期
+    expire_time: Option<DateTime<Utc>>,
+}
+
+impl Verifier {
+    /// 应答和回调通知的时间戳与当前时间相差超过 5 分钟时视为过期
+    const TOLERANCE: i64 = 5 * 60;
+
+    pub(crate) async fn add_public_key(&self, id: &str, public_key: &str) -> Result<()> {
+        let key = RsaPublicKey::from_public_key_pem(public_key)?;
+
+        self.keys.write().await.insert(
+            id.to_uppercase(),
+            Key {
+                key,
+                expire_time: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// 添加平台证书并移除已过期的平台证书，返回证书序列号
+    pub(crate) async fn add_certificate(&self, certificate: &str) -> Result<String> {
+        let (serial, key) = Self::parse_certificate(certificate)?;
+
+        self.remove_expired().await;
+
+        self.keys.write().await.insert(serial.clone(), key);
+
+        Ok(serial)
+    }
+
+    /// 下载平台证书的应答可能由其中的新证书签名，使用新证书和已缓存的证书验签，
+    /// 验签通过后才缓存新证书并移除已过期的平台证书
+    pub(crate) async fn add_signed_certificates(
+        &self,
+        certificates: &[&str],
+        signed: &Signed<'_>,
+        body: &[u8],
+    ) -> Result<()> {
+        let keys = certificates
+            .iter()
+            .map(|certificate| Self::parse_certificate(certificate))
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        {
+            let serial = signed.serial.to_uppercase();
+            let guard = self.keys.read().await;
+
+            Self::verify_with(
+                keys.get(&serial).or_else(|| guard.get(&serial)),
+                signed,
+                body,
+            )?;
+        }
+
+        self.remove_expired().await;
+
+        self.keys.write().await.extend(keys);
+
+        Ok(())
+    }
+
+    fn parse_certificate(certificate: &str) -> Result<(String, Key)> {
+        let certificate = Certificate::from_pem(certificate)?;
+
+        let serial = hex::encode_upper(certificate.tbs_certificate.serial_number.as_bytes());
+
+        let expire_time = DateTime::<Utc>::from(
+            certificate
+                .tbs_certificate
+                .validity
+                .not_after
+                .to_system_time(),
+        );
+
+        let der = certificate
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()?;
+
+        let key = RsaPublicKey::from_public_key_der(&der)?;
+
+        Ok((
+            serial,
+            Key {
+                key,
+                expire_time: Some(expire_time),
+            },
+        ))
+    }
+
+    /// 移除已过期的平台证书，微信支付公钥没有有效期，不会被移除
+    pub(crate) async fn remove_expired(&self) {
+        let now = Utc::now();
+
+        self.keys
+            .write()
+            .await
+            .retain(|_, key| key.expire_time.is_none_or(|expire_time| expire_time > now));
+    }
+
+    pub(crate) async fn is_empty(&self) -> bool {
+        self.keys.read().await.is_empty()
+    }
+
+    pub(crate) async fn contains(&self, serial: &str) -> bool {
+        self.keys.read().await.contains_key(&serial.to_uppercase())
+    }
+
+    /// 验证签名，签名串为 `timestamp\nnonce\nbody\n`
+    /// https://pay.weixin.qq.com/docs/merchant/development/interface-rules/signature-verification.html
+    pub(crate) async fn verify(&self, signed: &Signed<'_>, body: &[u8]) -> Result<()> {
+        let guard = self.keys.read().await;
+
+        Self::verify_with(guard.get(&signed.serial.to_uppercase()), signed, body)
+    }
+
+    /// 使用序列号对应的证书或公钥验签，`key` 为 `None` 时序列号未知
+    fn verify_with(key: Option<&Key>, signed: &Signed<'_>, body: &[u8]) -> Result<()> {
+        let timestamp = signed
+            .timestamp
+            .parse::<i64>()
+            .map_err(|_| PayVerification(format!("invalid timestamp {}", signed.timestamp)))?;
+
+        if (sign::timestamp() - timestamp).abs() > Self::TOLERANCE {
+            return Err(PayVerification(format!("expired timestamp {}", timestamp)));
+        }
+
+        let key =
+            key.ok_or_else(|| PayVerification(format!("unknown serial {}", signed.serial)))?;
+
+        if key
+            .expire_time
+            .is_some_and(|expire_time| expire_time <= Utc::now())
+        {
+            return Err(PayVerification(format!("expired serial {}", signed.serial)));
+        }
+
+        let signature = Signature::try_from(STANDARD.decode(signed.signature)?.as_slice())
+            .map_err(|error| PayVerification(error.to_string()))?;
+
+        let mut message = format!("{}\n{}\n", signed.timestamp, signed.nonce).into_bytes();
+        message.extend_from_slice(body);
+        message.push(b'\n');
+
+        VerifyingKey::<Sha256>::new(key.key.clone())
+            .verify(&message, &signature)
+            .map_err(|error| PayVerification(error.to_string()))?;
+
+        event!(Level::DEBUG, "verified by serial {}", signed.serial);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, id::OpenId, Result};
+
+#[derive(Serialize)]
+struct CoverRequest<'a> {
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+    ctoken: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoverBuilder {
+    data: CoverData,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoverData {
+    url: String,
+}
+
+/// 获取用户领取红包封面的链接，`ctoken` 为红包封面的领取凭证
+/// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/red-packet-cover/getRedPacketCoverUrl.html
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use open_wechat::{
+///     credential::GenericAccessToken, id::OpenId, redpacket::GetAuthenticatedCover, Result,
+/// };
+/// use serde_json::json;
+///
+/// pub(crate) async fn cover(
+///     State(access_token): State<GenericAccessToken>,
+/// ) -> Result<impl IntoResponse> {
+///     let url = access_token
+///         .get_authenticated_cover(&OpenId::new("oUpF8uMuAJO_M2pxb1Q9zNjWeS6o")?, "ctoken")
+///         .await?;
+///
+///     Ok(Json(json!({
+///         "url": url,
+///     })))
+/// }
+/// ```
+#[async_trait]
+pub trait GetAuthenticatedCover {
+    const AUTHENTICATED_COVER: &'static str =
+        "https://api.weixin.qq.com/redpacketcover/wxapp/cover_url/get_by_token";
+
+    async fn get_authenticated_cover(&self, open_id: &OpenId, ctoken: &str) -> Result<String>;
+}
+
+#[async_trait]
+impl<T> GetAuthenticatedCover for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, ctoken))]
+    async fn get_authenticated_cover(&self, open_id: &OpenId, ctoken: &str) -> Result<String> {
+        let builder = self
+            .client()
+            .post::<_, CoverBuilder>(
+                Self::AUTHENTICATED_COVER,
+                &self.token().await?,
+                &CoverRequest {
+                    open_id: open_id.as_str(),
+                    ctoken,
+                },
+            )
+            .await?;
+
+        event!(Level::DEBUG, "cover url: {}", builder.data.url);
+
+        Ok(builder.data.url)
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    future::Future,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use tokio::sync::Notify;
+use tracing::{event, Level};
+
+use crate::Result;
+
+/// 缓存的凭据过期时只允许一个调用者刷新，其余调用者等待刷新完成后重新读取缓存，
+/// access_token、JS-SDK 临时票据和微信支付平台证书共用此逻辑
+#[derive(Debug, Default)]
+pub(crate) struct RefreshOnce {
+    refreshing: AtomicBool,
+    notify: Notify,
+}
+
+impl RefreshOnce {
+    /// `cached` 返回有效的缓存，返回 `None` 时由一个调用者执行 `refresh`
+    pub(crate) async fn get<T, C, CF, R, RF>(&self, mut cached: C, refresh: R) -> Result<T>
+    where
+        C: FnMut() -> CF,
+        CF: Future<Output = Option<T>>,
+        R: FnOnce() -> RF,
+        RF: Future<Output = Result<T>>,
+    {
+        loop {
+            if let Some(value) = cached().await {
+                return Ok(value);
+            }
+
+            // 在检查刷新状态之前创建，确保不会错过刷新完成的通知
+            let notified = self.notify.notified();
+
+            if self.refreshing.swap(true, Ordering::AcqRel) {
+                event!(Level::DEBUG, "refreshing");
+
+                notified.await;
+
+                continue;
+            }
+
+            // 刷新的调用者被取消时（如客户端断开或超时）也要重置状态并唤醒等待者
+            let _guard = RefreshGuard(self);
+
+            event!(Level::DEBUG, "prepare to refresh");
+
+            return refresh().await;
+        }
+    }
+}
+
+/// 释放时结束刷新并唤醒等待刷新的调用者
+struct RefreshGuard<'a>(&'a RefreshOnce);
+
+impl Drop for RefreshGuard<'_> {
+    fn drop(&mut self) {
+        self.0.refreshing.store(false, Ordering::Release);
+
+        self.0.notify.notify_waiters();
+    }
+}
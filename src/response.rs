@@ -1,22 +1,65 @@
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, de::Error as _, Deserialize, Deserializer};
+use serde_json::Value;
 use tracing::{event, Level};
 
-use crate::{error::ErrorCode, Result};
+use crate::{error::Error, error::ErrorCode, Result};
 
 /// 微信小程序返回的数据结构
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
+#[derive(Debug)]
 pub(crate) enum Response<T> {
     Success {
-        #[serde(flatten)]
         data: T,
     },
     Error {
-        #[serde(rename = "errcode")]
         code: ErrorCode,
-        #[serde(rename = "errmsg")]
         message: String,
     },
+    /// 未收录在 [`ErrorCode`] 中的错误码
+    Unknown {
+        code: i64,
+        message: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct Flatten<T> {
+    #[serde(flatten)]
+    data: T,
+}
+
+impl<'de, T> Deserialize<'de> for Response<T>
+where
+    T: DeserializeOwned,
+{
+    // `errcode` 为 0 或不存在时表示成功，部分接口成功时也会返回 `errcode: 0`
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        let code = value
+            .get("errcode")
+            .and_then(Value::as_i64)
+            .unwrap_or_default();
+
+        if code == 0 {
+            let Flatten { data } = Flatten::<T>::deserialize(value).map_err(D::Error::custom)?;
+
+            return Ok(Self::Success { data });
+        }
+
+        let message = value
+            .get("errmsg")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        match ErrorCode::deserialize(&value["errcode"]) {
+            Ok(code) => Ok(Self::Error { code, message }),
+            Err(_) => Ok(Self::Unknown { code, message }),
+        }
+    }
 }
 
 impl<T> Response<T> {
@@ -34,6 +77,16 @@ impl<T> Response<T> {
 
                 Err((code, message).into())
             }
+            Self::Unknown { code, message } => {
+                event!(
+                    Level::ERROR,
+                    "微信小程序返回错误: code={}, message={}",
+                    code,
+                    message
+                );
+
+                Err(Error::Unknown(code, message))
+            }
         }
     }
 }
@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_repr::Serialize_repr;
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, id::OpenId, Result};
+
+/// 风控场景
+#[derive(Debug, Serialize_repr, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum RiskScene {
+    /// 注册
+    #[default]
+    Register = 0,
+    /// 营销作弊
+    Marketing = 1,
+}
+
+/// 用户安全等级查询参数
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct UserRiskQuery {
+    pub scene: RiskScene,
+    /// 用户手机号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mobile_no: Option<String>,
+    /// 用户访问源 ip
+    pub client_ip: String,
+    /// 用户邮箱地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+    /// 用户银行卡号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_card_no: Option<String>,
+    /// 额外补充信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_info: Option<String>,
+    /// 为 true 时表示测试调用，不计入调用次数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_test: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct UserRiskRequest<'a> {
+    #[serde(rename = "appid")]
+    app_id: &'a str,
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+    #[serde(flatten)]
+    query: &'a UserRiskQuery,
+}
+
+/// 用户安全等级
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserRisk {
+    risk_rank: u8,
+    unoin_id: i64,
+}
+
+impl UserRisk {
+    /// 用户风险等级，合法值为 0 到 4，数字越大风险越高
+    pub fn risk_rank(&self) -> u8 {
+        self.risk_rank
+    }
+
+    /// 唯一请求标识，标记单次请求，字段名 `unoin_id` 与微信接口保持一致
+    pub fn unoin_id(&self) -> i64 {
+        self.unoin_id
+    }
+}
+
+/// 获取用户安全等级，用户需在近两小时访问过小程序
+/// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/safety-control-capability/getUserRiskRank.html
+#[async_trait]
+pub trait GetUserRiskRank {
+    const USER_RISK_RANK: &'static str = "https://api.weixin.qq.com/wxa/getuserriskrank";
+
+    async fn get_user_risk_rank(&self, open_id: &OpenId, query: &UserRiskQuery)
+        -> Result<UserRisk>;
+}
+
+#[async_trait]
+impl<T> GetUserRiskRank for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, query))]
+    async fn get_user_risk_rank(
+        &self,
+        open_id: &OpenId,
+        query: &UserRiskQuery,
+    ) -> Result<UserRisk> {
+        let request = UserRiskRequest {
+            app_id: self.client().app_id(),
+            open_id: open_id.as_str(),
+            query,
+        };
+
+        let risk = self
+            .client()
+            .post::<_, UserRisk>(Self::USER_RISK_RANK, &self.token().await?, &request)
+            .await?;
+
+        event!(Level::DEBUG, "user risk: {:#?}", risk);
+
+        Ok(risk)
+    }
+}
@@ -0,0 +1,137 @@
+//! `tower` 集成，需要开启 `tower` feature
+//!
+//! [`TransportService`] 将 [`HttpTransport`] 作为 `tower::Service`，叠加超时、限流、并发限制等 layer 后，
+//! 再通过 [`ServiceTransport`] 交给 [`Client::with_transport`](crate::client::Client::with_transport)
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use open_wechat::{
+//!     client::Client,
+//!     service::{ServiceTransport, TransportService},
+//!     transport::ReqwestTransport,
+//! };
+//! use tower::ServiceBuilder;
+//!
+//! let service = ServiceBuilder::new()
+//!     .timeout(Duration::from_secs(5))
+//!     .concurrency_limit(16)
+//!     .service(TransportService::new(ReqwestTransport::default()));
+//!
+//! let client = Client::with_transport(
+//!     "your app id",
+//!     "your app secret",
+//!     ServiceTransport::new(service),
+//! );
+//! ```
+
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tower::Service;
+
+use crate::{
+    error::Error::{self, Transport},
+    transport::{HttpRequest, HttpResponse, HttpTransport},
+    Result,
+};
+
+/// `tower` 中间件返回的错误类型
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// 将 [`HttpTransport`] 作为 `tower::Service`，用作 layer 的最内层
+#[derive(Debug)]
+pub struct TransportService<T> {
+    transport: Arc<T>,
+}
+
+impl<T> TransportService<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport: Arc::new(transport),
+        }
+    }
+}
+
+impl<T> Clone for TransportService<T> {
+    fn clone(&self) -> Self {
+        Self {
+            transport: self.transport.clone(),
+        }
+    }
+}
+
+impl<T> Service<HttpRequest> for TransportService<T>
+where
+    T: HttpTransport + 'static,
+{
+    type Response = HttpResponse;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<HttpResponse>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: HttpRequest) -> Self::Future {
+        let transport = self.transport.clone();
+
+        Box::pin(async move { transport.send(request).await })
+    }
+}
+
+/// 将 `tower::Service` 作为 [`HttpTransport`]，layer 返回的错误转换为 [`Error::Transport`]
+pub struct ServiceTransport<S> {
+    // `poll_ready` 和 `call` 需要独占 service，限流等 layer 的状态在所有请求之间共享
+    service: Mutex<S>,
+}
+
+impl<S> ServiceTransport<S> {
+    pub fn new(service: S) -> Self {
+        Self {
+            service: Mutex::new(service),
+        }
+    }
+}
+
+impl<S> fmt::Debug for ServiceTransport<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceTransport").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl<S> HttpTransport for ServiceTransport<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse> + Send,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+{
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let future = {
+            let mut service = self.service.lock().await;
+
+            poll_fn(|cx| service.poll_ready(cx))
+                .await
+                .map_err(into_error)?;
+
+            service.call(request)
+        };
+
+        future.await.map_err(into_error)
+    }
+}
+
+/// 内层 [`TransportService`] 返回的错误保持原样，其余错误如超时、过载转换为 [`Error::Transport`]
+fn into_error(error: impl Into<BoxError>) -> Error {
+    match error.into().downcast::<Error>() {
+        Ok(error) => *error,
+        Err(error) => Transport(error),
+    }
+}
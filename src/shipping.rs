@@ -0,0 +1,580 @@
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use tracing::{event, instrument, Level};
+
+use crate::{credential::TokenProvider, id::OpenId, Result};
+
+/// 订单，`transaction_id` 和 `mch_id` + `out_trade_no` 二选一
+#[derive(Debug, Serialize, Clone)]
+pub struct OrderKey {
+    order_number_type: OrderNumberType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_id: Option<String>,
+    #[serde(rename = "mchid", skip_serializing_if = "Option::is_none")]
+    mch_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    out_trade_no: Option<String>,
+}
+
+impl OrderKey {
+    /// 使用微信支付订单号
+    pub fn transaction_id(transaction_id: &str) -> Self {
+        Self {
+            order_number_type: OrderNumberType::TransactionId,
+            transaction_id: Some(transaction_id.into()),
+            mch_id: None,
+            out_trade_no: None,
+        }
+    }
+
+    /// 使用商户号和商户系统内部订单号
+    pub fn out_trade_no(mch_id: &str, out_trade_no: &str) -> Self {
+        Self {
+            order_number_type: OrderNumberType::OutTradeNo,
+            transaction_id: None,
+            mch_id: Some(mch_id.into()),
+            out_trade_no: Some(out_trade_no.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize_repr, Clone, Copy)]
+#[repr(u8)]
+enum OrderNumberType {
+    OutTradeNo = 1,
+    TransactionId = 2,
+}
+
+/// 物流模式
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LogisticsType {
+    /// 实体物流配送，采用快递公司进行实体物流配送形式
+    Express = 1,
+    /// 同城配送
+    IntraCity = 2,
+    /// 虚拟商品，如话费充值、点卡等，无实体配送形式
+    Virtual = 3,
+    /// 用户自提
+    SelfPickup = 4,
+}
+
+/// 发货模式
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DeliveryMode {
+    /// 统一发货
+    Unified = 1,
+    /// 分拆发货
+    Split = 2,
+}
+
+/// 物流信息
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct Shipping {
+    /// 物流单号，物流快递发货时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracking_no: Option<String>,
+    /// 物流公司编码，快递公司 ID，物流快递发货时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub express_company: Option<String>,
+    /// 商品信息，例如：微信红包抱枕*1个，限 120 个字以内
+    pub item_desc: String,
+    /// 联系方式，当发货的物流公司为顺丰时，联系方式为必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<ShippingContact>,
+}
+
+/// 联系方式，需要掩码，例如：189****1234
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ShippingContact {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consignor_contact: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receiver_contact: Option<String>,
+}
+
+/// 发货信息
+#[derive(Debug, Serialize, Clone)]
+pub struct ShippingInfo {
+    pub order_key: OrderKey,
+    pub logistics_type: LogisticsType,
+    pub delivery_mode: DeliveryMode,
+    /// 分拆发货模式时必填，用于标识分拆发货模式下是否已全部发货完成
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_all_delivered: Option<bool>,
+    /// 物流信息列表，最多 15 个
+    pub shipping_list: Vec<Shipping>,
+    /// 上传时间
+    pub upload_time: DateTime<FixedOffset>,
+}
+
+/// 合单发货信息
+#[derive(Debug, Serialize, Clone)]
+pub struct CombinedShippingInfo {
+    /// 合单订单
+    pub order_key: OrderKey,
+    /// 子单发货信息，最多 50 个
+    pub sub_orders: Vec<SubOrderShippingInfo>,
+    /// 上传时间
+    pub upload_time: DateTime<FixedOffset>,
+}
+
+/// 子单发货信息
+#[derive(Debug, Serialize, Clone)]
+pub struct SubOrderShippingInfo {
+    pub order_key: OrderKey,
+    pub logistics_type: LogisticsType,
+    pub delivery_mode: DeliveryMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_all_delivered: Option<bool>,
+    pub shipping_list: Vec<Shipping>,
+}
+
+#[derive(Serialize)]
+struct Payer<'a> {
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+}
+
+#[derive(Serialize)]
+struct ShippingInfoRequest<'a> {
+    #[serde(flatten)]
+    info: &'a ShippingInfo,
+    payer: Payer<'a>,
+}
+
+#[derive(Serialize)]
+struct CombinedShippingInfoRequest<'a> {
+    #[serde(flatten)]
+    info: &'a CombinedShippingInfo,
+    payer: Payer<'a>,
+}
+
+/// 查询订单，`transaction_id` 和 `merchant_id` + `merchant_trade_no` 二选一
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct OrderQuery {
+    /// 原支付交易对应的微信订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    /// 支付下单商户的商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_id: Option<String>,
+    /// 二级商户号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_merchant_id: Option<String>,
+    /// 商户系统内部订单号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_trade_no: Option<String>,
+}
+
+/// 查询订单列表
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct OrderListQuery {
+    /// 支付时间所属范围
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pay_time_range: Option<TimeRange>,
+    /// 订单状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_state: Option<OrderState>,
+    /// 支付者 openid
+    #[serde(rename = "openid", skip_serializing_if = "Option::is_none")]
+    pub open_id: Option<OpenId>,
+    /// 翻页时使用，获取第一页时不用传入，如果查询结果中 `has_more` 为 true，则需要传入该值以获取下一页
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_index: Option<String>,
+    /// 翻页时使用，返回列表的长度，默认为 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+}
+
+/// 时间范围，秒级时间戳
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TimeRange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub begin_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<i64>,
+}
+
+/// 订单状态
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OrderState {
+    /// 待发货
+    Pending = 1,
+    /// 已发货
+    Shipped = 2,
+    /// 确认收货
+    Received = 3,
+    /// 交易完成
+    Completed = 4,
+    /// 已退款
+    Refunded = 5,
+    /// 资金待结算
+    Settling = 6,
+}
+
+/// 订单
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Order {
+    transaction_id: String,
+    merchant_id: String,
+    #[serde(default)]
+    sub_merchant_id: String,
+    merchant_trade_no: String,
+    description: String,
+    paid_amount: u64,
+    #[serde(rename(deserialize = "openid"))]
+    open_id: OpenId,
+    trade_create_time: i64,
+    pay_time: i64,
+    #[serde(default)]
+    in_complaint: bool,
+    order_state: OrderState,
+    shipping: Option<OrderShipping>,
+}
+
+impl Order {
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    pub fn merchant_id(&self) -> &str {
+        &self.merchant_id
+    }
+
+    pub fn sub_merchant_id(&self) -> &str {
+        &self.sub_merchant_id
+    }
+
+    pub fn merchant_trade_no(&self) -> &str {
+        &self.merchant_trade_no
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// 支付单实际支付金额，单位为分
+    pub fn paid_amount(&self) -> u64 {
+        self.paid_amount
+    }
+
+    pub fn open_id(&self) -> &OpenId {
+        &self.open_id
+    }
+
+    /// 交易创建时间，秒级时间戳
+    pub fn trade_create_time(&self) -> i64 {
+        self.trade_create_time
+    }
+
+    /// 支付时间，秒级时间戳
+    pub fn pay_time(&self) -> i64 {
+        self.pay_time
+    }
+
+    /// 是否处在交易纠纷中
+    pub fn in_complaint(&self) -> bool {
+        self.in_complaint
+    }
+
+    pub fn order_state(&self) -> OrderState {
+        self.order_state
+    }
+
+    /// 发货信息
+    pub fn shipping(&self) -> Option<&OrderShipping> {
+        self.shipping.as_ref()
+    }
+}
+
+/// 订单的发货信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderShipping {
+    delivery_mode: DeliveryMode,
+    logistics_type: LogisticsType,
+    finish_shipping: bool,
+    goods_desc: String,
+    finish_shipping_count: u32,
+    #[serde(default)]
+    shipping_list: Vec<ShippingDetail>,
+}
+
+impl OrderShipping {
+    pub fn delivery_mode(&self) -> DeliveryMode {
+        self.delivery_mode
+    }
+
+    pub fn logistics_type(&self) -> LogisticsType {
+        self.logistics_type
+    }
+
+    /// 是否已完成全部发货
+    pub fn finish_shipping(&self) -> bool {
+        self.finish_shipping
+    }
+
+    /// 在小程序后台发货信息管理页面手动发货时填写的商品描述
+    pub fn goods_desc(&self) -> &str {
+        &self.goods_desc
+    }
+
+    /// 已完成全部发货的次数，未完成时为 0，完成时为 1，重新发货并完成后为 2
+    pub fn finish_shipping_count(&self) -> u32 {
+        self.finish_shipping_count
+    }
+
+    pub fn shipping_list(&self) -> &[ShippingDetail] {
+        &self.shipping_list
+    }
+}
+
+/// 已上传的物流信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShippingDetail {
+    #[serde(default)]
+    tracking_no: String,
+    #[serde(default)]
+    express_company: String,
+    goods_desc: String,
+    upload_time: i64,
+    contact: Option<ShippingContact>,
+}
+
+impl ShippingDetail {
+    pub fn tracking_no(&self) -> &str {
+        &self.tracking_no
+    }
+
+    pub fn express_company(&self) -> &str {
+        &self.express_company
+    }
+
+    pub fn goods_desc(&self) -> &str {
+        &self.goods_desc
+    }
+
+    /// 上传时间，秒级时间戳
+    pub fn upload_time(&self) -> i64 {
+        self.upload_time
+    }
+
+    pub fn contact(&self) -> Option<&ShippingContact> {
+        self.contact.as_ref()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderBuilder {
+    order: Order,
+}
+
+/// 订单列表
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderList {
+    last_index: String,
+    has_more: bool,
+    order_list: Vec<Order>,
+}
+
+impl OrderList {
+    /// 翻页时使用
+    pub fn last_index(&self) -> &str {
+        &self.last_index
+    }
+
+    /// 是否还有更多支付单
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    pub fn order_list(&self) -> &[Order] {
+        &self.order_list
+    }
+}
+
+#[derive(Serialize)]
+struct ConfirmReceiveRequest<'a> {
+    #[serde(flatten)]
+    query: &'a OrderQuery,
+    received_time: i64,
+}
+
+#[derive(Serialize)]
+struct JumpPathRequest<'a> {
+    path: &'a str,
+}
+
+/// 小程序发货信息管理服务，用户支付后需要及时录入发货信息，否则平台将暂停资金结算
+/// https://developers.weixin.qq.com/miniprogram/dev/platform-capabilities/business-capabilities/order-shipping/order-shipping.html
+/// ```rust
+/// use axum::{extract::State, response::IntoResponse, Json};
+/// use chrono::Local;
+/// use open_wechat::{
+///     credential::GenericAccessToken,
+///     shipping::{
+///         DeliveryMode, LogisticsType, ManageShipping, OrderKey, Shipping, ShippingInfo,
+///     },
+///     id::OpenId,
+///     Result,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// pub(crate) struct Delivery {
+///     transaction_id: String,
+///     open_id: OpenId,
+///     item_desc: String,
+/// }
+///
+/// pub(crate) async fn deliver(
+///     State(access_token): State<GenericAccessToken>,
+///     Json(delivery): Json<Delivery>,
+/// ) -> Result<impl IntoResponse> {
+///     let info = ShippingInfo {
+///         order_key: OrderKey::transaction_id(&delivery.transaction_id),
+///         logistics_type: LogisticsType::Virtual,
+///         delivery_mode: DeliveryMode::Unified,
+///         is_all_delivered: None,
+///         shipping_list: vec![Shipping {
+///             item_desc: delivery.item_desc,
+///             ..Default::default()
+///         }],
+///         upload_time: Local::now().fixed_offset(),
+///     };
+///
+///     access_token
+///         .upload_shipping_info(&delivery.open_id, &info)
+///         .await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[async_trait]
+pub trait ManageShipping {
+    const UPLOAD_SHIPPING_INFO: &'static str =
+        "https://api.weixin.qq.com/wxa/sec/order/upload_shipping_info";
+    const UPLOAD_COMBINED_SHIPPING_INFO: &'static str =
+        "https://api.weixin.qq.com/wxa/sec/order/upload_combined_shipping_info";
+    const GET_ORDER: &'static str = "https://api.weixin.qq.com/wxa/sec/order/get_order";
+    const GET_ORDER_LIST: &'static str = "https://api.weixin.qq.com/wxa/sec/order/get_order_list";
+    const NOTIFY_CONFIRM_RECEIVE: &'static str =
+        "https://api.weixin.qq.com/wxa/sec/order/notify_confirm_receive";
+    const SET_MSG_JUMP_PATH: &'static str =
+        "https://api.weixin.qq.com/wxa/sec/order/set_msg_jump_path";
+
+    /// 发货信息录入，`open_id` 为支付者的 openid
+    async fn upload_shipping_info(&self, open_id: &OpenId, info: &ShippingInfo) -> Result<()>;
+
+    /// 发货信息合单录入，`open_id` 为支付者的 openid
+    async fn upload_combined_shipping_info(
+        &self,
+        open_id: &OpenId,
+        info: &CombinedShippingInfo,
+    ) -> Result<()>;
+
+    /// 查询订单发货状态
+    async fn get_order(&self, query: &OrderQuery) -> Result<Order>;
+
+    /// 查询订单列表
+    async fn get_order_list(&self, query: &OrderListQuery) -> Result<OrderList>;
+
+    /// 确认收货提醒，`received_time` 为快递签收时间，秒级时间戳
+    async fn notify_confirm_receive(&self, query: &OrderQuery, received_time: i64) -> Result<()>;
+
+    /// 消息跳转路径设置，用户点击发货消息时跳转到小程序的页面路径
+    async fn set_msg_jump_path(&self, path: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl<T> ManageShipping for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, info))]
+    async fn upload_shipping_info(&self, open_id: &OpenId, info: &ShippingInfo) -> Result<()> {
+        let request = ShippingInfoRequest {
+            info,
+            payer: Payer {
+                open_id: open_id.as_str(),
+            },
+        };
+
+        self.client()
+            .post(Self::UPLOAD_SHIPPING_INFO, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self, info))]
+    async fn upload_combined_shipping_info(
+        &self,
+        open_id: &OpenId,
+        info: &CombinedShippingInfo,
+    ) -> Result<()> {
+        let request = CombinedShippingInfoRequest {
+            info,
+            payer: Payer {
+                open_id: open_id.as_str(),
+            },
+        };
+
+        self.client()
+            .post(
+                Self::UPLOAD_COMBINED_SHIPPING_INFO,
+                &self.token().await?,
+                &request,
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_order(&self, query: &OrderQuery) -> Result<Order> {
+        let builder = self
+            .client()
+            .post::<_, OrderBuilder>(Self::GET_ORDER, &self.token().await?, query)
+            .await?;
+
+        event!(Level::DEBUG, "order: {:#?}", builder.order);
+
+        Ok(builder.order)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_order_list(&self, query: &OrderListQuery) -> Result<OrderList> {
+        let list = self
+            .client()
+            .post::<_, OrderList>(Self::GET_ORDER_LIST, &self.token().await?, query)
+            .await?;
+
+        event!(Level::DEBUG, "order list: {:#?}", list);
+
+        Ok(list)
+    }
+
+    #[instrument(skip(self))]
+    async fn notify_confirm_receive(&self, query: &OrderQuery, received_time: i64) -> Result<()> {
+        let request = ConfirmReceiveRequest {
+            query,
+            received_time,
+        };
+
+        self.client()
+            .post(Self::NOTIFY_CONFIRM_RECEIVE, &self.token().await?, &request)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn set_msg_jump_path(&self, path: &str) -> Result<()> {
+        self.client()
+            .post(
+                Self::SET_MSG_JUMP_PATH,
+                &self.token().await?,
+                &JumpPathRequest { path },
+            )
+            .await
+    }
+}
@@ -0,0 +1,281 @@
+//! 可替换的 HTTP 传输层，[`Client`](crate::client::Client) 的所有请求都通过 [`HttpTransport`] 发送，
+//! 默认使用 [`ReqwestTransport`]，也可以接入自有的 HTTP 客户端或在测试中返回固定的应答，
+//! 请求和应答使用 `http` 和 `url` crate 的类型，关闭默认的 `reqwest` feature 后不依赖 reqwest
+
+use std::fmt;
+
+use async_trait::async_trait;
+use http::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method, StatusCode,
+};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+use crate::{
+    error::Error::{InternalServer, Transport},
+    response::Response,
+    Result,
+};
+
+/// 发送 HTTP 请求，返回完整的应答
+/// ```rust
+/// use std::sync::Mutex;
+///
+/// use async_trait::async_trait;
+/// use open_wechat::{
+///     client::Client,
+///     id::LoginCode,
+///     transport::{HttpRequest, HttpResponse, HttpTransport},
+///     Result,
+/// };
+/// use http::{HeaderMap, StatusCode};
+///
+/// /// 不发送请求，记录请求的路径并返回固定的应答
+/// #[derive(Debug, Default)]
+/// struct MemoryTransport {
+///     paths: Mutex<Vec<String>>,
+/// }
+///
+/// #[async_trait]
+/// impl HttpTransport for MemoryTransport {
+///     async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+///         self.paths.lock().unwrap().push(request.url.path().into());
+///
+///         Ok(HttpResponse {
+///             status: StatusCode::OK,
+///             headers: HeaderMap::new(),
+///             body: br#"{"openid":"oUpF8uMuAJO_M2pxb1Q9zNjWeS6o","session_key":"tiihtNczf5v6AKRyjwEUhQ=="}"#
+///                 .to_vec(),
+///         })
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let client = Client::with_transport("your app id", "your app secret", MemoryTransport::default());
+///
+///     let credential = client.login(&LoginCode::new("your_login_code")?).await?;
+///
+///     assert_eq!(credential.open_id().as_str(), "oUpF8uMuAJO_M2pxb1Q9zNjWeS6o");
+///
+///     Ok(())
+/// }
+/// ```
+#[async_trait]
+pub trait HttpTransport: fmt::Debug + Send + Sync {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// 待发送的 HTTP 请求
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// `url` 无法解析时返回 [`Transport`](crate::error::Error::Transport)
+    pub(crate) fn new(method: Method, url: &str) -> Result<Self> {
+        Ok(Self {
+            method,
+            url: Url::parse(url).map_err(|error| Transport(error.into()))?,
+            headers: HeaderMap::new(),
+            body: None,
+        })
+    }
+
+    pub(crate) fn get(url: &str) -> Result<Self> {
+        Self::new(Method::GET, url)
+    }
+
+    pub(crate) fn post(url: &str) -> Result<Self> {
+        Self::new(Method::POST, url)
+    }
+
+    /// 追加查询参数
+    pub(crate) fn query<Q>(mut self, query: &Q) -> Result<Self>
+    where
+        Q: Serialize + ?Sized,
+    {
+        {
+            let mut pairs = self.url.query_pairs_mut();
+
+            query.serialize(serde_urlencoded::Serializer::new(&mut pairs))?;
+        }
+
+        if self.url.query() == Some("") {
+            self.url.set_query(None);
+        }
+
+        Ok(self)
+    }
+
+    pub(crate) fn body(mut self, content_type: &'static str, body: Vec<u8>) -> Self {
+        self.headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        self.body = Some(body);
+
+        self
+    }
+
+    /// 请求体为 JSON
+    pub(crate) fn json<B>(self, body: &B) -> Result<Self>
+    where
+        B: Serialize + ?Sized,
+    {
+        Ok(self.body("application/json", serde_json::to_vec(body)?))
+    }
+
+    /// 请求体为 multipart/form-data
+    pub(crate) fn multipart(mut self, multipart: Multipart) -> Result<Self> {
+        let (content_type, body) = multipart.finish();
+
+        let content_type =
+            HeaderValue::from_str(&content_type).map_err(|error| Transport(error.into()))?;
+
+        self.headers.insert(CONTENT_TYPE, content_type);
+        self.body = Some(body);
+
+        Ok(self)
+    }
+}
+
+impl fmt::Debug for HttpRequest {
+    // 查询参数中可能包含 access_token 或 secret，不打印
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field(
+                "url",
+                &format_args!(
+                    "{}{}",
+                    self.url.origin().ascii_serialization(),
+                    self.url.path()
+                ),
+            )
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("body", &self.body.as_ref().map(Vec::len))
+            .finish()
+    }
+}
+
+/// HTTP 应答
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub(crate) fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// 获取微信返回的数据，HTTP 状态码不为 2xx 时返回 [`InternalServer`](crate::error::Error::InternalServer)
+    pub(crate) fn extract<T>(self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        if self.status.is_success() {
+            serde_json::from_slice::<Response<T>>(&self.body)?.extract()
+        } else {
+            Err(InternalServer(self.text()))
+        }
+    }
+}
+
+/// multipart/form-data 请求体
+#[derive(Debug)]
+pub(crate) struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    pub(crate) fn new() -> Self {
+        let boundary = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+
+        Self {
+            boundary,
+            body: Vec::new(),
+        }
+    }
+
+    /// 添加文件
+    pub(crate) fn file(mut self, name: &str, file_name: &str, bytes: &[u8]) -> Self {
+        self.body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                self.boundary, name, file_name
+            )
+            .as_bytes(),
+        );
+        self.body.extend_from_slice(bytes);
+        self.body.extend_from_slice(b"\r\n");
+
+        self
+    }
+
+    fn finish(mut self) -> (String, Vec<u8>) {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+
+        (
+            format!("multipart/form-data; boundary={}", self.boundary),
+            self.body,
+        )
+    }
+}
+
+/// 基于 `reqwest::Client` 的默认传输层，需要启用 `reqwest` feature
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    /// 使用自定义的 `reqwest::Client`，如配置代理、超时或连接池
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> Self {
+        Self::new(client)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+
+        Ok(HttpResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+}
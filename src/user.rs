@@ -1,13 +1,13 @@
-use std::collections::HashMap;
-
 use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hex::encode;
 use serde::{Deserialize, Serialize};
-use tracing::{event, Level};
+use sha2::{Digest, Sha256};
+use tracing::{event, instrument, Level};
 
 use crate::{
-    credential::{GenericAccessToken, GetAccessToken, GetStableAccessToken, StableAccessToken},
-    error::Error::InternalServer,
-    response::Response,
+    credential::TokenProvider,
+    id::{AppId, OpenId, UnionId},
     Result,
 };
 
@@ -47,7 +47,7 @@ impl User {
         &self.avatar
     }
 
-    pub fn app_id(&self) -> &str {
+    pub fn app_id(&self) -> &AppId {
         &self.watermark.app_id
     }
 
@@ -105,7 +105,7 @@ impl Contact {
         &self.country_code
     }
 
-    pub fn app_id(&self) -> &str {
+    pub fn app_id(&self) -> &AppId {
         &self.watermark.app_id
     }
 
@@ -144,14 +144,14 @@ struct PhoneInner {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct Watermark {
-    app_id: String,
+    app_id: AppId,
     timestamp: u64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 struct WatermarkBuilder {
     #[serde(rename = "appid")]
-    app_id: String,
+    app_id: AppId,
     timestamp: u64,
 }
 
@@ -168,85 +168,196 @@ impl WatermarkBuilder {
 pub trait GetContact {
     const PHONE: &str = "https://api.weixin.qq.com/wxa/business/getuserphonenumber";
 
-    async fn get_contact(&self, code: &str, open_id: Option<&str>) -> Result<Contact>;
+    async fn get_contact(&self, code: &str, open_id: Option<&OpenId>) -> Result<Contact>;
 }
 
-#[async_trait]
-impl GetContact for GenericAccessToken {
-    async fn get_contact(&self, code: &str, open_id: Option<&str>) -> Result<Contact> {
-        use reqwest::Client;
+#[derive(Serialize)]
+pub(crate) struct ContactRequest<'a> {
+    pub(crate) code: &'a str,
+    #[serde(rename = "openid", skip_serializing_if = "Option::is_none")]
+    pub(crate) open_id: Option<&'a str>,
+}
 
-        event!(Level::DEBUG, "code: {}, open_id: {:?}", code, open_id);
+#[async_trait]
+impl<T> GetContact for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, code))]
+    async fn get_contact(&self, code: &str, open_id: Option<&OpenId>) -> Result<Contact> {
+        let request = ContactRequest {
+            code,
+            open_id: open_id.map(OpenId::as_str),
+        };
+
+        let builder = self
+            .client()
+            .post::<_, ContactBuilder>(Self::PHONE, &self.token().await?, &request)
+            .await?;
 
-        let mut query = HashMap::new();
-        let mut body = HashMap::new();
+        event!(Level::DEBUG, "contact builder: {:#?}", builder);
 
-        query.insert("access_token", self.access_token().await?);
-        body.insert("code", code);
+        Ok(builder.build())
+    }
+}
 
-        if let Some(open_id) = open_id {
-            body.insert("openid", open_id);
-        }
+/// 支付订单，`transaction_id` 和 `mch_id` + `out_trade_no` 二选一
+#[derive(Debug, Serialize, Clone)]
+#[serde(untagged)]
+pub enum PaidOrder {
+    /// 微信支付订单号
+    TransactionId { transaction_id: String },
+    /// 微信支付商户订单号和微信支付商户号
+    OutTradeNo {
+        mch_id: String,
+        out_trade_no: String,
+    },
+}
 
-        let response = Client::new()
-            .post(Self::PHONE)
-            .query(&query)
-            .json(&body)
-            .send()
-            .await?;
+#[derive(Serialize)]
+struct PaidUnionIdQuery<'a> {
+    #[serde(rename = "openid")]
+    open_id: &'a str,
+    #[serde(flatten)]
+    order: &'a PaidOrder,
+}
 
-        event!(Level::DEBUG, "response: {:#?}", response);
+#[derive(Debug, Deserialize)]
+struct PaidUnionIdBuilder {
+    #[serde(rename = "unionid")]
+    union_id: UnionId,
+}
 
-        if response.status().is_success() {
-            let response = response.json::<Response<ContactBuilder>>().await?;
+#[async_trait]
+pub trait GetPaidUnionId {
+    const PAID_UNION_ID: &'static str = "https://api.weixin.qq.com/wxa/getpaidunionid";
 
-            let builder = response.extract()?;
+    /// 用户支付完成后，获取该用户的 unionid，无需用户关注公众号，支付完成后 5 分钟内有效
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/getPaidUnionid.html
+    async fn get_paid_union_id(&self, open_id: &OpenId, order: &PaidOrder) -> Result<UnionId>;
+}
 
-            event!(Level::DEBUG, "contact builder: {:#?}", builder);
+#[async_trait]
+impl<T> GetPaidUnionId for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self))]
+    async fn get_paid_union_id(&self, open_id: &OpenId, order: &PaidOrder) -> Result<UnionId> {
+        let query = PaidUnionIdQuery {
+            open_id: open_id.as_str(),
+            order,
+        };
+
+        let builder = self
+            .client()
+            .get::<_, PaidUnionIdBuilder>(Self::PAID_UNION_ID, &self.token().await?, &query)
+            .await?;
 
-            Ok(builder.build())
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+        Ok(builder.union_id)
     }
 }
 
+#[derive(Serialize)]
+struct PluginOpenPidRequest<'a> {
+    code: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginOpenPidBuilder {
+    #[serde(rename = "openpid")]
+    open_pid: String,
+}
+
 #[async_trait]
-impl GetContact for GenericAccessToken<StableAccessToken> {
-    async fn get_contact(&self, code: &str, open_id: Option<&str>) -> Result<Contact> {
-        use reqwest::Client;
+pub trait GetPluginOpenPid {
+    const PLUGIN_OPEN_PID: &'static str = "https://api.weixin.qq.com/wxa/getpluginopenpid";
 
-        event!(Level::DEBUG, "code: {}, open_id: {:?}", code, open_id);
+    /// 通过插件用户的 code 获取插件用户的 openpid
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/basic-info/getPluginOpenPId.html
+    async fn get_plugin_open_pid(&self, code: &str) -> Result<String>;
+}
 
-        let mut query = HashMap::new();
-        let mut body = HashMap::new();
+#[async_trait]
+impl<T> GetPluginOpenPid for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, code))]
+    async fn get_plugin_open_pid(&self, code: &str) -> Result<String> {
+        let builder = self
+            .client()
+            .post::<_, PluginOpenPidBuilder>(
+                Self::PLUGIN_OPEN_PID,
+                &self.token().await?,
+                &PluginOpenPidRequest { code },
+            )
+            .await?;
 
-        query.insert("access_token", self.access_token().await?);
-        body.insert("code", code);
+        Ok(builder.open_pid)
+    }
+}
 
-        if let Some(open_id) = open_id {
-            body.insert("openid", open_id);
-        }
+/// 加密数据的校验结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncryptedDataCheck {
+    #[serde(rename(deserialize = "vaild"))]
+    valid: bool,
+    create_time: i64,
+}
 
-        let response = Client::new()
-            .post(Self::PHONE)
-            .query(&query)
-            .json(&body)
-            .send()
-            .await?;
+impl EncryptedDataCheck {
+    /// 是否是合法的数据，字段名在微信接口中为 `vaild`
+    pub fn valid(&self) -> bool {
+        self.valid
+    }
 
-        event!(Level::DEBUG, "response: {:#?}", response);
+    /// 加密数据生成的时间戳
+    pub fn create_time(&self) -> i64 {
+        self.create_time
+    }
+}
+
+#[derive(Serialize)]
+struct EncryptedDataCheckRequest {
+    encrypted_msg_hash: String,
+}
 
-        if response.status().is_success() {
-            let response = response.json::<Response<ContactBuilder>>().await?;
+#[async_trait]
+pub trait CheckEncryptedData {
+    const CHECK_ENCRYPTED_DATA: &'static str =
+        "https://api.weixin.qq.com/wxa/business/checkencryptedmsg";
+
+    /// 检查加密信息是否由微信生成，仅支持检查最近 3 天内生成的加密数据，
+    /// `encrypted_data` 为小程序获取到的 Base64 编码的加密数据
+    /// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/internet/checkEncryptedData.html
+    async fn check_encrypted_data(&self, encrypted_data: &str) -> Result<EncryptedDataCheck>;
+}
 
-            let builder = response.extract()?;
+#[async_trait]
+impl<T> CheckEncryptedData for T
+where
+    T: TokenProvider,
+{
+    #[instrument(skip(self, encrypted_data))]
+    async fn check_encrypted_data(&self, encrypted_data: &str) -> Result<EncryptedDataCheck> {
+        let encrypted_data = STANDARD.decode(encrypted_data.as_bytes())?;
+
+        let request = EncryptedDataCheckRequest {
+            encrypted_msg_hash: encode(Sha256::digest(&encrypted_data)),
+        };
+
+        let check = self
+            .client()
+            .post::<_, EncryptedDataCheck>(
+                Self::CHECK_ENCRYPTED_DATA,
+                &self.token().await?,
+                &request,
+            )
+            .await?;
 
-            event!(Level::DEBUG, "contact builder: {:#?}", builder);
+        event!(Level::DEBUG, "encrypted data check: {:#?}", check);
 
-            Ok(builder.build())
-        } else {
-            Err(InternalServer(response.text().await?))
-        }
+        Ok(check)
     }
 }